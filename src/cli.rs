@@ -0,0 +1,564 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+use std::sync::Arc;
+
+use crossword_compressor::clues::Clue;
+use crossword_compressor::crossword_json::{self, LoadedCrossword};
+use crossword_compressor::ipuz;
+use crossword_compressor::letters::{self, Normalisation};
+use crossword_compressor::progress::Progress;
+use crossword_compressor::scorer::{
+    Area, AspectRatio, BoundingBox, Crossings, Density, Perimeter, Scorer, Weighted,
+};
+use crossword_compressor::svg::SvgOptions;
+use crossword_compressor::{
+    CrossData, Crossword, Direction, Heuristic, MaxSize, Seed, Selection, SolverOptions,
+};
+
+pub const EXIT_SOLUTION_FOUND: u8 = 0;
+pub const EXIT_NO_CROSSWORD: u8 = 1;
+pub const EXIT_BAD_INPUT: u8 = 2;
+pub const EXIT_STOPPED_EARLY: u8 = 3;
+
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+pub const USAGE: &str = "\
+Usage: crossword_compressor [OPTIONS] [WORDS]...
+
+Compresses a selection of words into a crossword.
+Words are read from the positional arguments, from --words-file, or from stdin
+(one word per line) when neither is given or when the only word is '-'.
+In a file or on stdin, a word may be followed by a tab and its clue, and by
+another tab and an enumeration such as (4,3).
+
+Options:
+  -f, --words-file <PATH>  Read words from a file, one word per line
+      --block-overlaps     Also search for overlaps of 2x2 and larger (slower)
+      --strip-accents      Let letters cross the same letter without its accent, e.g. é and e
+      --digraphs <LIST>    Put each of these digraphs in a single cell, e.g. ll,ch for Welsh
+      --join-words         Allow answers of several words, leaving out spaces, hyphens and
+                           apostrophes from the grid
+  -j, --threads <N>        Search with N threads; 0 uses every core [default: 1]
+  -t, --time-limit <SECS>  Stop after SECS seconds and show the best options so far
+      --transposition-limit <N>
+                           Remember up to N partial crosswords, split between the threads,
+                           skipping any reached again by placing the same words in another
+                           order [default: 1048576]
+      --heuristics <LIST>  Try some placements first, to find good options sooner: longest
+                           (longer words), connectable (words sharing more letters with the
+                           others) or central (crossings nearer the middle), e.g. longest,central
+      --progress           Report search statistics on stderr every second
+  -q, --quiet              Only print the final options
+  -v, --verbose            Also report search statistics, as --progress
+      --seed <SEED>        Pin a word in place, as WORD:ROW:START:DIRECTION, e.g. hereby:0:0:across
+      --seeds-file <PATH>  Read seeds from a file, one 'WORD ROW START DIRECTION' per line,
+                           or take every word of a saved .json or .ipuz crossword
+      --json <PATH>        Also save the options to PATH as JSON
+      --load <PATH>        Show the crosswords saved in PATH as JSON or .ipuz instead of searching
+      --ipuz <PATH>        Also save the options as ipuz puzzles, numbering the files if there are several
+      --puz <PATH>         Also save the options as Across Lite .puz files
+      --svg <PATH>         Also draw the options as SVG, numbering the files if there are several
+      --svg-letters        Fill in the letters in the SVG grids
+      --cell-size <PX>     Width of an SVG grid square [default: 32]
+      --font <FAMILY>      Font of the SVG clue numbers and letters
+  -s, --scorer <SPEC>      How to choose the best options [default: bounding-box]
+      --pareto             Show every option not beaten on both width and height
+      --pareto-crossings   As --pareto, also comparing the number of crossings
+  -m, --max-size <WxH>     Only allow crosswords up to W letters wide and H letters tall
+      --rotatable          Also allow crosswords which fit --max-size once turned sideways
+  -h, --help               Print this message
+
+Scorers:
+  bounding-box   Smaller longest side, then smaller shortest side
+  area           Smaller width x height
+  perimeter      Smaller width + height
+  aspect:RATIO   Longest side / shortest side closer to RATIO
+  crossings      More crossing letters
+  density        Larger fraction of the grid filled
+  Combine scorers with weights, e.g. --scorer 2*area,crossings
+
+Seeds:
+  ROW is the y coordinate of an Across word and the x coordinate of a Down word.
+  START is the x coordinate of the first letter of an Across word, or its y coordinate if Down.
+  Without seeds, the first word is placed Across at 0:0.
+
+Exit codes:
+  0  At least one crossword was found
+  1  No valid crossword exists for the words
+  2  Bad input
+  3  The search stopped before any crossword was found";
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum WordSource {
+    Arguments(Vec<String>),
+    File(String),
+    Stdin,
+}
+
+/// How much the binary prints while searching.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Verbosity {
+    /// Only the final options.
+    Quiet,
+    /// Every option as it is found, and when the selection improves.
+    #[default]
+    Normal,
+    /// As `Normal`, with search statistics on stderr.
+    Verbose,
+}
+
+#[derive(Debug)]
+pub struct Arguments {
+    pub help: bool,
+    pub verbosity: Verbosity,
+    pub source: WordSource,
+    pub options: SolverOptions,
+    pub seeds: Vec<Seed>,
+    pub seeds_file: Option<String>,
+    pub json_file: Option<String>,
+    pub load_file: Option<String>,
+    pub svg_file: Option<String>,
+    pub ipuz_file: Option<String>,
+    pub puz_file: Option<String>,
+    pub svg_options: SvgOptions,
+}
+
+pub fn parse(args: &[String]) -> Result<Arguments, String> {
+    let mut help = false;
+    let mut verbosity = Verbosity::default();
+    let mut words_file = None;
+    let mut words = Vec::new();
+    let mut options = SolverOptions::default();
+    let mut rotatable = false;
+    let mut seeds = Vec::new();
+    let mut seeds_file = None;
+    let mut json_file = None;
+    let mut load_file = None;
+    let mut svg_file = None;
+    let mut ipuz_file = None;
+    let mut puz_file = None;
+    let mut svg_options = SvgOptions::default();
+
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        match arg.as_str() {
+            "-h" | "--help" => help = true,
+            "-q" | "--quiet" => verbosity = Verbosity::Quiet,
+            "-v" | "--verbose" => verbosity = Verbosity::Verbose,
+            "-f" | "--words-file" => match args_iter.next() {
+                Some(path) => words_file = Some(path.clone()),
+                None => return Err(format!("{} requires a path", arg)),
+            },
+            "--block-overlaps" => options.block_overlaps = true,
+            "--strip-accents" => options.normalisation.strip_diacritics = true,
+            "--digraphs" => match args_iter.next() {
+                Some(list) => options.normalisation.digraphs = parse_digraphs(list)?,
+                None => return Err(format!("{} requires a value", arg)),
+            },
+            "--join-words" => options.normalisation.drop_separators = true,
+            "-j" | "--threads" => options.threads = parse_value(arg, args_iter.next())?,
+            "--heuristics" => match args_iter.next() {
+                Some(list) => options.heuristics = parse_heuristics(list)?,
+                None => return Err(format!("{} requires a value", arg)),
+            },
+            "--transposition-limit" => {
+                options.transposition_limit = parse_value(arg, args_iter.next())?
+            }
+            "-s" | "--scorer" => match args_iter.next() {
+                Some(spec) => options.scorer = parse_scorer(spec)?,
+                None => return Err(format!("{} requires a value", arg)),
+            },
+            "--pareto" => options.selection = Selection::ParetoFront { crossings: false },
+            "--pareto-crossings" => options.selection = Selection::ParetoFront { crossings: true },
+            "-m" | "--max-size" => match args_iter.next() {
+                Some(size) => options.max_size = Some(parse_max_size(arg, size)?),
+                None => return Err(format!("{} requires a value", arg)),
+            },
+            "--rotatable" => rotatable = true,
+            "--seed" => match args_iter.next() {
+                Some(seed) => seeds.push(parse_seed(&seed.split(':').collect::<Vec<_>>())?),
+                None => return Err(format!("{} requires a value", arg)),
+            },
+            "--seeds-file" => match args_iter.next() {
+                Some(path) => seeds_file = Some(path.clone()),
+                None => return Err(format!("{} requires a path", arg)),
+            },
+            "--json" => match args_iter.next() {
+                Some(path) => json_file = Some(path.clone()),
+                None => return Err(format!("{} requires a path", arg)),
+            },
+            "--load" => match args_iter.next() {
+                Some(path) => load_file = Some(path.clone()),
+                None => return Err(format!("{} requires a path", arg)),
+            },
+            "--svg" => match args_iter.next() {
+                Some(path) => svg_file = Some(path.clone()),
+                None => return Err(format!("{} requires a path", arg)),
+            },
+            "--ipuz" => match args_iter.next() {
+                Some(path) => ipuz_file = Some(path.clone()),
+                None => return Err(format!("{} requires a path", arg)),
+            },
+            "--puz" => match args_iter.next() {
+                Some(path) => puz_file = Some(path.clone()),
+                None => return Err(format!("{} requires a path", arg)),
+            },
+            "--svg-letters" => svg_options.show_letters = true,
+            "--cell-size" => {
+                let cell_size: f64 = parse_value(arg, args_iter.next())?;
+                if !(cell_size > 0.0 && cell_size.is_finite()) {
+                    return Err(format!("invalid value '{}' for {}", cell_size, arg));
+                }
+                svg_options.number_font_size *= cell_size / svg_options.cell_size;
+                svg_options.letter_font_size *= cell_size / svg_options.cell_size;
+                svg_options.cell_size = cell_size;
+            }
+            "--font" => match args_iter.next() {
+                Some(font_family) => svg_options.font_family = font_family.clone(),
+                None => return Err(format!("{} requires a value", arg)),
+            },
+            "-t" | "--time-limit" => {
+                let seconds: f64 = parse_value(arg, args_iter.next())?;
+                match Duration::try_from_secs_f64(seconds) {
+                    Ok(time_limit) => options.time_limit = Some(time_limit),
+                    Err(_) => return Err(format!("invalid value '{}' for {}", seconds, arg)),
+                }
+            }
+            "--progress" => options.progress = Some(Progress::stderr(PROGRESS_INTERVAL)),
+            "-" => words.push(arg.clone()),
+            _ if arg.starts_with('-') => return Err(format!("unknown option {}", arg)),
+            _ => words.push(arg.clone()),
+        }
+    }
+
+    if verbosity == Verbosity::Verbose && options.progress.is_none() {
+        options.progress = Some(Progress::stderr(PROGRESS_INTERVAL));
+    }
+
+    match &mut options.max_size {
+        Some(max_size) => max_size.rotatable = rotatable,
+        None if rotatable => return Err("--rotatable requires --max-size".to_string()),
+        None => {}
+    }
+
+    if load_file.is_some()
+        && (words_file.is_some() || !words.is_empty() || !seeds.is_empty() || seeds_file.is_some())
+    {
+        return Err("--load cannot be combined with words or seeds".to_string());
+    }
+
+    let source = match (words_file, words.as_slice()) {
+        (Some(_), [_, ..]) => {
+            return Err("words cannot be given both as arguments and in a file".to_string())
+        }
+        (Some(path), []) => WordSource::File(path),
+        (None, []) => WordSource::Stdin,
+        (None, [word]) if word == "-" => WordSource::Stdin,
+        (None, _) => WordSource::Arguments(words),
+    };
+
+    Ok(Arguments {
+        help,
+        verbosity,
+        source,
+        options,
+        seeds,
+        seeds_file,
+        json_file,
+        load_file,
+        svg_file,
+        ipuz_file,
+        puz_file,
+        svg_options,
+    })
+}
+
+fn parse_value<T: FromStr>(option: &str, value: Option<&String>) -> Result<T, String> {
+    match value {
+        Some(value) => value
+            .parse()
+            .map_err(|_| format!("invalid value '{}' for {}", value, option)),
+        None => Err(format!("{} requires a value", option)),
+    }
+}
+
+fn parse_digraphs(list: &str) -> Result<Vec<String>, String> {
+    list.split(',')
+        .map(|digraph| {
+            let digraph = digraph.trim().to_lowercase();
+            match letters::get_digraph_cell(&digraph) {
+                Some(_) => Ok(digraph),
+                None => Err(format!(
+                    "unknown digraph '{}', expected one of {}",
+                    digraph,
+                    letters::DIGRAPHS.join(",")
+                )),
+            }
+        })
+        .collect()
+}
+
+fn parse_heuristics(list: &str) -> Result<Vec<Heuristic>, String> {
+    list.split(',')
+        .map(|name| match name.trim() {
+            "longest" => Ok(Heuristic::Longest),
+            "connectable" => Ok(Heuristic::Connectable),
+            "central" => Ok(Heuristic::Central),
+            name => Err(format!(
+                "unknown heuristic '{}', expected one of longest,connectable,central",
+                name
+            )),
+        })
+        .collect()
+}
+
+fn parse_max_size(option: &str, size: &str) -> Result<MaxSize, String> {
+    let invalid = || {
+        format!(
+            "invalid value '{}' for {}, expected e.g. 15x15",
+            size, option
+        )
+    };
+
+    let (width, height) = size.split_once(['x', 'X']).ok_or_else(invalid)?;
+    let width = width.parse().map_err(|_| invalid())?;
+    let height = height.parse().map_err(|_| invalid())?;
+    if width == 0 || height == 0 {
+        return Err(invalid());
+    }
+
+    Ok(MaxSize {
+        width,
+        height,
+        rotatable: false,
+    })
+}
+
+fn parse_seed(fields: &[&str]) -> Result<Seed, String> {
+    let invalid = || format!("invalid seed '{}'", fields.join(" "));
+
+    let [word, row, start_point, direction] = fields else {
+        return Err(invalid());
+    };
+    let direction = match direction.to_lowercase().as_str() {
+        "a" | "across" => Direction::Across,
+        "d" | "down" => Direction::Down,
+        _ => return Err(invalid()),
+    };
+
+    let cross_data = CrossData {
+        row: row.parse().map_err(|_| invalid())?,
+        start_point: start_point.parse().map_err(|_| invalid())?,
+        direction,
+        order: 0,
+    };
+    if !cross_data.fits(word) {
+        return Err(format!("seed '{}' is out of range", fields.join(" ")));
+    }
+
+    Ok(Seed {
+        word: word.to_string(),
+        row: cross_data.row,
+        start_point: cross_data.start_point,
+        direction,
+    })
+}
+
+fn parse_scorer(spec: &str) -> Result<Arc<dyn Scorer>, String> {
+    let mut scorers = Vec::new();
+
+    for part in spec.split(',') {
+        let (weight, name) = match part.split_once('*') {
+            Some((weight, name)) => match weight.trim().parse::<f64>() {
+                Ok(weight) if weight >= 0.0 => (weight, name.trim()),
+                _ => return Err(format!("invalid weight in scorer '{}'", part)),
+            },
+            None => (1.0, part.trim()),
+        };
+
+        let scorer: Arc<dyn Scorer> = match name.split_once(':') {
+            Some(("aspect", ratio)) => match ratio.parse::<f64>() {
+                Ok(target) if target > 0.0 => Arc::new(AspectRatio { target }),
+                _ => return Err(format!("invalid ratio in scorer '{}'", part)),
+            },
+            _ => match name {
+                "bounding-box" => Arc::new(BoundingBox),
+                "area" => Arc::new(Area),
+                "perimeter" => Arc::new(Perimeter),
+                "crossings" => Arc::new(Crossings),
+                "density" => Arc::new(Density),
+                _ => return Err(format!("unknown scorer '{}'", name)),
+            },
+        };
+
+        scorers.push((weight, scorer));
+    }
+
+    match scorers.as_slice() {
+        [(weight, scorer)] if *weight == 1.0 => Ok(scorer.clone()),
+        _ => Ok(Arc::new(Weighted { scorers })),
+    }
+}
+
+/// Reads the words, with the clues given in a file or on stdin as `WORD<TAB>CLUE`, optionally
+/// followed by `<TAB>ENUMERATION`. Words may only contain spaces if they are to be left out.
+pub fn read_words(
+    source: &WordSource,
+    normalisation: &Normalisation,
+) -> Result<(Vec<String>, Vec<Option<Clue>>), String> {
+    let (words, clues) = match source {
+        WordSource::Arguments(words) => (words.clone(), vec![None; words.len()]),
+        WordSource::File(path) => {
+            let text = fs::read_to_string(path)
+                .map_err(|error| format!("cannot read {}: {}", path, error))?;
+            split_lines(&text)
+                .iter()
+                .map(|line| split_clue(line))
+                .unzip()
+        }
+        WordSource::Stdin => {
+            let mut text = String::new();
+            io::stdin()
+                .read_to_string(&mut text)
+                .map_err(|error| format!("cannot read stdin: {}", error))?;
+            split_lines(&text)
+                .iter()
+                .map(|line| split_clue(line))
+                .unzip()
+        }
+    };
+
+    check_words(&words, normalisation)?;
+
+    Ok((words, clues))
+}
+
+fn split_clue(line: &str) -> (String, Option<Clue>) {
+    let mut fields = line.splitn(3, '\t').map(str::trim);
+    let word = fields.next().unwrap_or_default().to_string();
+    let text = fields.next().unwrap_or_default();
+    let enumeration = fields.next().filter(|enumeration| !enumeration.is_empty());
+
+    let clue = if text.is_empty() && enumeration.is_none() {
+        None
+    } else {
+        Some(Clue {
+            text: text.to_string(),
+            enumeration: enumeration.map(String::from),
+        })
+    };
+
+    (word, clue)
+}
+
+pub fn read_seeds(arguments: &Arguments) -> Result<Vec<Seed>, String> {
+    let mut seeds = arguments.seeds.clone();
+
+    if let Some(path) = &arguments.seeds_file {
+        if is_ipuz(path) || path.to_lowercase().ends_with(".json") {
+            for loaded in read_crosswords(path, &arguments.options)? {
+                seeds.extend(loaded.seeds());
+            }
+            return Ok(seeds);
+        }
+
+        let text =
+            fs::read_to_string(path).map_err(|error| format!("cannot read {}: {}", path, error))?;
+        for line in split_lines(&text) {
+            seeds.push(parse_seed(&line.split_whitespace().collect::<Vec<_>>())?);
+        }
+    }
+
+    Ok(seeds)
+}
+
+pub fn read_crosswords(
+    path: &str,
+    options: &SolverOptions,
+) -> Result<Vec<LoadedCrossword>, String> {
+    let text =
+        fs::read_to_string(path).map_err(|error| format!("cannot read {}: {}", path, error))?;
+
+    let loaded = if is_ipuz(path) {
+        ipuz::from_ipuz(&text, options).map(|loaded| vec![loaded])
+    } else {
+        crossword_json::from_json(&text, options)
+    };
+    loaded.map_err(|error| format!("{}: {}", path, error))
+}
+
+fn is_ipuz(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("ipuz"))
+}
+
+pub fn write_crosswords(path: &str, crosswords: &[Crossword]) -> Result<(), String> {
+    fs::write(path, crossword_json::to_json_list(crosswords))
+        .map_err(|error| format!("cannot write {}: {}", path, error))
+}
+
+/// Writes one file per crossword. When there are several, they are numbered from 1 before the
+/// extension, as in `grid-1.svg`.
+pub fn write_files<C: AsRef<[u8]>>(
+    path: &str,
+    crosswords: &[Crossword],
+    render: impl Fn(&Crossword) -> Result<C, String>,
+) -> Result<(), String> {
+    for (index, crossword) in crosswords.iter().enumerate() {
+        let path = if crosswords.len() == 1 {
+            path.to_string()
+        } else {
+            numbered_path(path, index + 1)
+        };
+        let contents = render(crossword).map_err(|message| format!("{}: {}", path, message))?;
+        fs::write(&path, contents).map_err(|error| format!("cannot write {}: {}", path, error))?;
+    }
+
+    Ok(())
+}
+
+fn numbered_path(path: &str, number: usize) -> String {
+    let path = Path::new(path);
+    let mut file_name = path.file_stem().unwrap_or_default().to_os_string();
+    file_name.push(format!("-{}", number));
+    if let Some(extension) = path.extension() {
+        file_name.push(".");
+        file_name.push(extension);
+    }
+
+    path.with_file_name(file_name).to_string_lossy().to_string()
+}
+
+fn split_lines(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+fn check_words(words: &[String], normalisation: &Normalisation) -> Result<(), String> {
+    if words.is_empty() {
+        return Err("no words given".to_string());
+    }
+
+    for word in words {
+        if word == "-" || letters::normalise(word, normalisation).is_empty() {
+            return Err(format!("invalid word '{}'", word));
+        }
+        if word.chars().any(char::is_whitespace) && !normalisation.drop_separators {
+            return Err(format!("word '{}' contains whitespace", word));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "./tests_cli.rs"]
+mod tests_cli;
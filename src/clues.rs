@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::crossword::{Crossword, Direction, WordCross};
+use crate::letters;
+
+/// The clue for a word. Without an enumeration such as "(4,3)", it is worked out from the word.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Clue {
+    pub text: String,
+    pub enumeration: Option<String>,
+}
+
+impl Clue {
+    pub fn get_enumeration(&self, word: &WordCross) -> String {
+        match &self.enumeration {
+            Some(enumeration) => enumeration.clone(),
+            None => get_lengths(word),
+        }
+    }
+}
+
+/// The number of cells in each part of a word, split where its display form has a space (",") or
+/// a hyphen ("-"), as in "(3,5)" for "ice cream". A digraph cell counts as one letter, and
+/// apostrophes are skipped.
+fn get_lengths(word: &WordCross) -> String {
+    let display = word.display.unwrap_or_default().to_lowercase();
+    let mut display = display.chars().peekable();
+    let mut lengths = String::from("(");
+    let mut length = 0;
+
+    for cell in word.word.chars() {
+        if !letters::SEPARATORS.contains(&cell) {
+            let mut separator = None;
+            while let Some(letter) = display.next_if(|letter| letters::SEPARATORS.contains(letter))
+            {
+                match letter {
+                    '-' => separator = Some('-'),
+                    ' ' => separator = separator.or(Some(',')),
+                    _ => {}
+                }
+            }
+            if let Some(separator) = separator.filter(|_| length > 0) {
+                write!(lengths, "{}{}", length, separator).unwrap();
+                length = 0;
+            }
+        }
+        for _ in letters::get_cell_text(cell).chars() {
+            display.next();
+        }
+        length += 1;
+    }
+
+    write!(lengths, "{})", length).unwrap();
+    lengths
+}
+
+/// A numbered word in the Across or Down list.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Entry<'a> {
+    pub number: usize,
+    pub word_index: usize,
+    pub word: &'a str,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Numbering<'a> {
+    /// The clue number of each cell which starts a word, keyed by `[x, y]`.
+    pub numbers: HashMap<[i32; 2], usize>,
+    pub across: Vec<Entry<'a>>,
+    pub down: Vec<Entry<'a>>,
+}
+
+/// Numbers the crossword in the usual way. Scanning from top to bottom and left to right, each
+/// cell which starts an Across or Down word takes the next number, shared by both directions.
+/// The lists are ordered by number.
+pub fn numbering<'a>(crossword: &Crossword<'a>) -> Numbering<'a> {
+    let mut starts: Vec<([i32; 2], usize, Direction)> = crossword
+        .words
+        .iter()
+        .enumerate()
+        .filter_map(|(word_index, word)| {
+            let cross_data = word.cross.as_ref()?;
+            Some((cross_data.get_position(), word_index, cross_data.direction))
+        })
+        .collect();
+    starts.sort_by_key(|([x, y], _, direction)| (*y, *x, direction.index()));
+
+    let mut numbering = Numbering::default();
+    for (position, word_index, direction) in starts {
+        let next_number = numbering.numbers.len() + 1;
+        let number = *numbering.numbers.entry(position).or_insert(next_number);
+
+        let entry = Entry {
+            number,
+            word_index,
+            word: crossword.words[word_index].word,
+        };
+        match direction {
+            Direction::Across => numbering.across.push(entry),
+            Direction::Down => numbering.down.push(entry),
+        }
+    }
+
+    numbering
+}
+
+/// Lists the clues under Across and Down headings, as printed below a finished puzzle. Words
+/// without a clue are listed with only their enumeration.
+pub fn format_clue_lists(crossword: &Crossword) -> String {
+    let numbering = numbering(crossword);
+    let mut output = String::new();
+
+    for (heading, entries) in [("Across", &numbering.across), ("Down", &numbering.down)] {
+        if entries.is_empty() {
+            continue;
+        }
+
+        output.push_str(heading);
+        output.push('\n');
+        for entry in entries {
+            let clue = crossword.words[entry.word_index]
+                .clue
+                .cloned()
+                .unwrap_or_default();
+            let enumeration = clue.get_enumeration(&crossword.words[entry.word_index]);
+            if clue.text.is_empty() {
+                writeln!(output, "{}. {}", entry.number, enumeration).unwrap();
+            } else {
+                writeln!(output, "{}. {} {}", entry.number, clue.text, enumeration).unwrap();
+            }
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+#[path = "./tests_clues.rs"]
+mod tests_clues;
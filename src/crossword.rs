@@ -1,4 +1,9 @@
+use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
+use std::fmt;
+
+use crate::clues::Clue;
+use crate::letters;
 
 const X: usize = 0;
 const Y: usize = 1;
@@ -20,6 +25,10 @@ pub struct CrossData {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct WordCross<'a> {
     pub word: &'a str,
+    pub clue: Option<&'a Clue>,
+    /// The word as it was given, when it differs from its cells by more than case, e.g. with
+    /// accents or spaces. See [`letters::normalise`].
+    pub display: Option<&'a str>,
     pub cross: Option<CrossData>,
 }
 
@@ -28,6 +37,13 @@ pub struct Crossword<'a> {
     pub words: Vec<WordCross<'a>>,
 }
 
+/// What a crossword looks like, whatever its position, the order its words were placed in, or
+/// which of two equal words fills which slot. The letters are moved so that the top left cell is
+/// at (0, 0), and the grid is transposed if that sorts first, as swapping Across and Down gives
+/// the same puzzle. Mirror images are not matched, as they read every word backwards.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct CanonicalForm(Vec<([i32; 2], char)>);
+
 impl Direction {
     pub fn index(&self) -> usize {
         match self {
@@ -45,27 +61,42 @@ impl Direction {
 }
 
 impl CrossData {
-    fn get_position(&self) -> [i32; 2] {
+    /// The `[x, y]` coordinates of the first letter.
+    pub fn get_position(&self) -> [i32; 2] {
         match self.direction {
             Direction::Across => [self.start_point, self.row],
             Direction::Down => [self.row, self.start_point],
         }
     }
+
+    /// Whether the coordinates of every cell of `word` fit in an `i32`, along with the cells
+    /// around it which the placement checks look at.
+    pub fn fits(&self, word: &str) -> bool {
+        let Ok(length) = i32::try_from(word.chars().count()) else {
+            return false;
+        };
+
+        self.row.checked_sub(1).is_some()
+            && self.row.checked_add(1).is_some()
+            && self.start_point.checked_sub(1).is_some()
+            && self.start_point.checked_add(length).is_some()
+    }
 }
 
 impl Crossword<'_> {
+    /// Every placed letter, with the row and mid point of a word crossing it the other way,
+    /// whether or not it can still be crossed. The search leaves out the letters already crossed,
+    /// and those next to a word running the way the new word would.
     pub fn get_crossable_letters(&self) -> Vec<(char, i32, i32, Direction)> {
         let mut output = Vec::new();
 
         for word in &self.words {
             if let Some(cross_data) = &word.cross {
                 let direction = cross_data.direction.change();
-                let mut row = cross_data.start_point;
                 let mid_point = cross_data.row;
 
-                for letter in word.word.chars() {
+                for (row, letter) in (cross_data.start_point..).zip(word.word.chars()) {
                     output.push((letter, row, mid_point, direction));
-                    row += 1;
                 }
             }
         }
@@ -77,10 +108,9 @@ impl Crossword<'_> {
         let mut next_order = 0;
         for word in &self.words {
             if let Some(cross_data) = &word.cross {
-                next_order = next_order.max(cross_data.order);
+                next_order = next_order.max(cross_data.order + 1);
             }
         }
-        next_order += 1;
 
         next_order
     }
@@ -94,6 +124,347 @@ impl Crossword<'_> {
         (min, max)
     }
 
+    /// The `[x, y]` coordinates of the top left corner of the bounding box.
+    pub fn get_origin(&self) -> [i32; 2] {
+        let (x_low, _, y_low, _) = self.get_x_y_width();
+
+        [x_low, y_low]
+    }
+
+    pub fn get_width_height(&self) -> (usize, usize) {
+        let (_, x_width, _, y_width) = self.get_x_y_width();
+
+        (x_width, y_width)
+    }
+
+    /// The smallest widths and heights the crossword could have once every word is placed. The
+    /// longest word not yet placed must fit across the width or down the height, and the others
+    /// then fit the same way.
+    pub fn get_size_bounds(&self) -> Vec<(usize, usize)> {
+        let (width, height) = self.get_width_height();
+        let longest = self
+            .words
+            .iter()
+            .filter(|word| word.cross.is_none())
+            .map(|word| word.word.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        if longest <= width || longest <= height {
+            vec![(width, height)]
+        } else {
+            vec![(longest, height), (width, longest)]
+        }
+    }
+
+    pub fn count_cells(&self) -> usize {
+        let mut cells = HashSet::new();
+
+        for word in &self.words {
+            if let Some(cross_data) = &word.cross {
+                cells.extend(get_cells(word.word, cross_data));
+            }
+        }
+
+        cells.len()
+    }
+
+    pub fn get_canonical_form(&self) -> CanonicalForm {
+        let letters = self.get_letters();
+        let [x_low, y_low] = if letters.is_empty() {
+            [0, 0]
+        } else {
+            self.get_origin()
+        };
+
+        // Cells are kept as [y, x], so that they sort row by row.
+        let mut cells: Vec<([i32; 2], char)> = letters
+            .into_iter()
+            .map(|([x, y], letter)| ([y - y_low, x - x_low], letter))
+            .collect();
+        cells.sort_unstable();
+        let mut transposed: Vec<([i32; 2], char)> = cells
+            .iter()
+            .map(|([y, x], letter)| ([*x, *y], *letter))
+            .collect();
+        transposed.sort_unstable();
+
+        CanonicalForm(cells.min(transposed))
+    }
+
+    /// The letter in each occupied cell, keyed by `[x, y]`.
+    pub fn get_letters(&self) -> HashMap<[i32; 2], char> {
+        let mut letters = HashMap::new();
+
+        for word in &self.words {
+            if let Some(cross_data) = &word.cross {
+                letters.extend(
+                    get_cells(word.word, cross_data)
+                        .into_iter()
+                        .zip(word.word.chars()),
+                );
+            }
+        }
+
+        letters
+    }
+
+    pub fn is_connected(&self) -> bool {
+        let mut cell_words: HashMap<[i32; 2], Vec<usize>> = HashMap::new();
+        let mut placed = Vec::new();
+
+        for (word_index, word) in self.words.iter().enumerate() {
+            if let Some(cross_data) = &word.cross {
+                for position in get_cells(word.word, cross_data) {
+                    cell_words.entry(position).or_default().push(word_index);
+                }
+                placed.push(word_index);
+            }
+        }
+
+        let mut connected = vec![false; self.words.len()];
+        let mut to_visit = placed.first().copied().into_iter().collect::<Vec<_>>();
+        while let Some(word_index) = to_visit.pop() {
+            if connected[word_index] {
+                continue;
+            }
+            connected[word_index] = true;
+
+            if let Some(cross_data) = &self.words[word_index].cross {
+                for position in get_cells(self.words[word_index].word, cross_data) {
+                    to_visit.extend(&cell_words[&position]);
+                }
+            }
+        }
+
+        placed.iter().all(|word_index| connected[*word_index])
+    }
+
+    /// The placed words in an order in which they can be inserted one by one, each crossing one
+    /// before it. The words with an order below `pinned`, or else the one with the lowest order,
+    /// come first as they are, and the others follow as near to breadth first from them as they
+    /// can. A word beside an earlier one only comes once the word joining them is in, or it would
+    /// run alongside it.
+    pub fn get_crossing_order(&self, pinned: usize) -> Vec<usize> {
+        let mut cell_words: HashMap<[i32; 2], Vec<usize>> = HashMap::new();
+        for (word_index, word) in self.words.iter().enumerate() {
+            if let Some(cross_data) = &word.cross {
+                for position in get_cells(word.word, cross_data) {
+                    cell_words.entry(position).or_default().push(word_index);
+                }
+            }
+        }
+
+        let breadth_first = self.get_breadth_first_order(&cell_words, pinned);
+        let n_pinned = breadth_first
+            .iter()
+            .filter(|&&word_index| self.words[word_index].cross.as_ref().unwrap().order < pinned)
+            .count()
+            .max(1)
+            .min(breadth_first.len());
+
+        let mut remaining = vec![false; self.words.len()];
+        for &word_index in &breadth_first {
+            remaining[word_index] = true;
+        }
+        // Words lying side by side, which only overlapping words allow, can go in any order.
+        if !breadth_first
+            .iter()
+            .all(|&word_index| self.is_joined(word_index, &cell_words, &remaining))
+        {
+            return breadth_first;
+        }
+
+        // Words are taken off the whole layout from the end of that order, but a word joining two
+        // others stays until one of them is gone. Taking a word off never keeps another on, so
+        // some word can always be taken off if any order works.
+        let mut removed = Vec::with_capacity(breadth_first.len());
+        while removed.len() + n_pinned < breadth_first.len() {
+            let candidates: Vec<usize> = breadth_first[n_pinned..]
+                .iter()
+                .rev()
+                .copied()
+                .filter(|&word_index| remaining[word_index])
+                .collect();
+            let mut removable = Vec::with_capacity(candidates.len());
+            let mut connected = Vec::with_capacity(candidates.len());
+            for &word_index in &candidates {
+                remaining[word_index] = false;
+                if (0..self.words.len())
+                    .filter(|&other| remaining[other])
+                    .all(|other| self.is_joined(other, &cell_words, &remaining))
+                {
+                    removable.push(word_index);
+                    if self.is_connected_within(&cell_words, &remaining) {
+                        connected.push(word_index);
+                    }
+                }
+                remaining[word_index] = true;
+            }
+
+            let next = connected
+                .first()
+                .or(removable.first())
+                .unwrap_or(&candidates[0]);
+            remaining[*next] = false;
+            removed.push(*next);
+        }
+
+        let mut order = breadth_first[..n_pinned].to_vec();
+        order.extend(removed.into_iter().rev());
+        order
+    }
+
+    /// The placed words breadth first from the words with an order below `pinned`, or else from
+    /// the one with the lowest order. Words not connected to them come last.
+    fn get_breadth_first_order(
+        &self,
+        cell_words: &HashMap<[i32; 2], Vec<usize>>,
+        pinned: usize,
+    ) -> Vec<usize> {
+        let mut placed: Vec<(usize, usize)> = self
+            .words
+            .iter()
+            .enumerate()
+            .filter_map(|(word_index, word)| Some((word.cross.as_ref()?.order, word_index)))
+            .collect();
+        placed.sort_unstable();
+
+        let mut visited = vec![false; self.words.len()];
+        let mut order = Vec::with_capacity(placed.len());
+        for &(word_order, word_index) in &placed {
+            if word_order < pinned {
+                visited[word_index] = true;
+                order.push(word_index);
+            }
+        }
+
+        let mut next = 0;
+        for (_, first) in placed {
+            if next == order.len() {
+                if visited[first] {
+                    continue;
+                }
+                visited[first] = true;
+                order.push(first);
+            }
+
+            while let Some(&word_index) = order.get(next) {
+                next += 1;
+                for position in self.get_word_cells(word_index) {
+                    for &other in &cell_words[&position] {
+                        if !visited[other] {
+                            visited[other] = true;
+                            order.push(other);
+                        }
+                    }
+                }
+            }
+        }
+
+        order
+    }
+
+    /// Whether the words crossing a word are placed wherever it touches a placed word, so that it
+    /// does not run alongside one, and it never touches placed words on one side at two cells in a
+    /// row.
+    fn is_joined(
+        &self,
+        word_index: usize,
+        cell_words: &HashMap<[i32; 2], Vec<usize>>,
+        is_placed: &[bool],
+    ) -> bool {
+        let Some(cross_data) = &self.words[word_index].cross else {
+            return false;
+        };
+        let index = cross_data.direction.change().index();
+
+        let mut touched = [false; 2];
+        self.get_word_cells(word_index).into_iter().all(|position| {
+            let touches = [-1, 1].map(|step| {
+                let mut neighbour = position;
+                neighbour[index] += step;
+                cell_words
+                    .get(&neighbour)
+                    .is_some_and(|others| others.iter().any(|&other| is_placed[other]))
+            });
+            let in_a_row = touches
+                .iter()
+                .zip(&touched)
+                .any(|(now, before)| now & before);
+            touched = touches;
+            !in_a_row
+                && (touches == [false; 2]
+                    || cell_words[&position]
+                        .iter()
+                        .all(|&other| other == word_index || is_placed[other]))
+        })
+    }
+
+    /// Whether the placed words all cross each other, directly or through other placed words.
+    fn is_connected_within(
+        &self,
+        cell_words: &HashMap<[i32; 2], Vec<usize>>,
+        is_placed: &[bool],
+    ) -> bool {
+        let Some(first) = is_placed.iter().position(|&placed| placed) else {
+            return true;
+        };
+
+        let mut visited = vec![false; self.words.len()];
+        visited[first] = true;
+        let mut stack = vec![first];
+        while let Some(word_index) = stack.pop() {
+            for position in self.get_word_cells(word_index) {
+                for &other in &cell_words[&position] {
+                    if is_placed[other] && !visited[other] {
+                        visited[other] = true;
+                        stack.push(other);
+                    }
+                }
+            }
+        }
+
+        visited == is_placed
+    }
+
+    fn get_word_cells(&self, word_index: usize) -> Vec<[i32; 2]> {
+        let word = &self.words[word_index];
+        match &word.cross {
+            Some(cross_data) => get_cells(word.word, cross_data),
+            None => Vec::new(),
+        }
+    }
+
+    /// Numbers the placed words by [`Crossword::get_crossing_order`], so that their order only
+    /// depends on the layout and the pinned words.
+    pub fn set_crossing_order(&mut self, pinned: usize) {
+        for (order, word_index) in self.get_crossing_order(pinned).into_iter().enumerate() {
+            if let Some(cross_data) = &mut self.words[word_index].cross {
+                cross_data.order = order;
+            }
+        }
+    }
+
+    pub fn count_crossings(&self) -> usize {
+        let n_letters: usize = self
+            .words
+            .iter()
+            .filter(|word| word.cross.is_some())
+            .map(|word| word.word.chars().count())
+            .sum();
+
+        n_letters - self.count_cells()
+    }
+
+    pub fn count_uncrossed_letters(&self) -> usize {
+        self.words
+            .iter()
+            .filter(|word| word.cross.is_none())
+            .map(|word| word.word.chars().count())
+            .sum()
+    }
+
     pub fn all_words_crossed(&self) -> bool {
         for word in &self.words {
             if word.cross.is_none() {
@@ -104,33 +475,45 @@ impl Crossword<'_> {
         true
     }
 
-    #[allow(clippy::needless_range_loop)]
-    pub fn print(&self) {
-        let (x_low, x_width, y_low, y_width) = self.get_x_y_width();
-
-        let mut grid = vec![vec![' '; y_width + 1]; x_width + 1];
+    pub fn is_valid(&self) -> bool {
+        let mut cells: HashMap<[i32; 2], (char, [Option<usize>; 2])> = HashMap::new();
 
-        for word in &self.words {
+        for (word_index, word) in self.words.iter().enumerate() {
             if let Some(cross_data) = &word.cross {
                 let mut position = cross_data.get_position();
                 let index = cross_data.direction.index();
 
-                for lr in word.word.chars() {
-                    grid[usize::try_from(position[X] - x_low).unwrap()]
-                        [usize::try_from(position[Y] - y_low).unwrap()] = lr;
+                for letter in word.word.chars() {
+                    let cell = cells.entry(position).or_insert((letter, [None, None]));
+                    if cell.0 != letter || cell.1[index].is_some() {
+                        return false;
+                    }
+                    cell.1[index] = Some(word_index);
                     position[index] += 1;
                 }
             }
         }
 
-        for y in 0..y_width {
-            for x in 0..x_width {
-                print!("{}", grid[x][y]);
+        for (position, (_, word_indices)) in &cells {
+            for index in [X, Y] {
+                let mut next_position = *position;
+                next_position[index] += 1;
+
+                if let Some((_, next_word_indices)) = cells.get(&next_position) {
+                    if word_indices[index].is_none()
+                        || word_indices[index] != next_word_indices[index]
+                    {
+                        return false;
+                    }
+                }
             }
-            println!();
         }
 
-        println!("\n");
+        true
+    }
+
+    pub fn print(&self) {
+        println!("{}\n", self);
     }
 
     fn get_x_y_width(&self) -> (i32, usize, i32, usize) {
@@ -161,28 +544,76 @@ impl Crossword<'_> {
             }
         }
 
-        let x_width = usize::try_from(x_high - x_low + 1).unwrap();
-        let y_width = usize::try_from(y_high - y_low + 1).unwrap();
+        let x_width = usize::try_from(i64::from(x_high) - i64::from(x_low) + 1).unwrap();
+        let y_width = usize::try_from(i64::from(y_high) - i64::from(y_low) + 1).unwrap();
 
         (x_low, x_width, y_low, y_width)
     }
 }
 
+/// The grid, one row per line, with a space for each empty cell.
+impl fmt::Display for Crossword<'_> {
+    #[allow(clippy::needless_range_loop)]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (x_low, x_width, y_low, y_width) = self.get_x_y_width();
+
+        let mut grid = vec![vec![' '; y_width + 1]; x_width + 1];
+
+        for word in &self.words {
+            if let Some(cross_data) = &word.cross {
+                let mut position = cross_data.get_position();
+                let index = cross_data.direction.index();
+
+                for lr in word.word.chars() {
+                    grid[usize::try_from(position[X] - x_low).unwrap()]
+                        [usize::try_from(position[Y] - y_low).unwrap()] = lr;
+                    position[index] += 1;
+                }
+            }
+        }
+
+        // Digraph cells are two letters wide, so widen every cell to keep the columns lined up.
+        let cell_width = grid
+            .iter()
+            .flatten()
+            .map(|cell| letters::get_cell_text(*cell).chars().count())
+            .max()
+            .unwrap_or(1);
+
+        for y in 0..y_width {
+            for x in 0..x_width {
+                write!(f, "{:<1$}", letters::get_cell_text(grid[x][y]), cell_width)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn get_cells(word: &str, cross_data: &CrossData) -> Vec<[i32; 2]> {
+    let mut position = cross_data.get_position();
+    let index = cross_data.direction.index();
+
+    let mut cells = Vec::with_capacity(word.chars().count());
+    for _ in word.chars() {
+        cells.push(position);
+        position[index] += 1;
+    }
+
+    cells
+}
+
 fn get_position_end(word: &str, cross_data: &CrossData) -> [i32; 2] {
     let mut position_end = cross_data.get_position();
     let index = cross_data.direction.index();
-    position_end[index] += word.len() as i32 - 1;
+    position_end[index] += word.chars().count() as i32 - 1;
 
     position_end
 }
 
-pub fn initialise(words: &[String]) -> Crossword {
-    let mut word_cross_vec = Vec::with_capacity(words.len());
-
-    for word in words {
-        let word_cross = WordCross { word, cross: None };
-        word_cross_vec.push(word_cross);
-    }
+pub fn initialise(words: &[String]) -> Crossword<'_> {
+    let mut crossword = initialise_empty(words);
 
     let first_word_cross_data = CrossData {
         row: 0,
@@ -190,7 +621,23 @@ pub fn initialise(words: &[String]) -> Crossword {
         direction: Direction::Across,
         order: 0,
     };
-    word_cross_vec[0].cross = Some(first_word_cross_data);
+    crossword.words[0].cross = Some(first_word_cross_data);
+
+    crossword
+}
+
+pub fn initialise_empty(words: &[String]) -> Crossword<'_> {
+    let mut word_cross_vec = Vec::with_capacity(words.len());
+
+    for word in words {
+        let word_cross = WordCross {
+            word,
+            clue: None,
+            display: None,
+            cross: None,
+        };
+        word_cross_vec.push(word_cross);
+    }
 
     Crossword {
         words: word_cross_vec,
@@ -199,4 +646,5 @@ pub fn initialise(words: &[String]) -> Crossword {
 
 #[cfg(test)]
 #[path = "./tests_crossword.rs"]
+#[allow(clippy::needless_return)]
 mod tests_crossword;
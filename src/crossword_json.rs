@@ -0,0 +1,343 @@
+use std::fmt;
+
+use crate::clues::Clue;
+use crate::crossword::{self, CrossData, Crossword, Direction};
+use crate::json::{self, Value};
+use crate::letters;
+use crate::options::place_word;
+use crate::solver::{Seed, SolverOptions};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LoadError {
+    /// The text is not valid JSON.
+    Syntax(String),
+    /// The JSON does not follow the crossword schema.
+    Schema(String),
+    /// The words clash with each other, or do not form a single crossword.
+    Layout(String),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Syntax(message) => write!(f, "invalid JSON: {}", message),
+            LoadError::Schema(message) => write!(f, "invalid crossword: {}", message),
+            LoadError::Layout(message) => write!(f, "invalid layout: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+/// A crossword loaded from JSON, which owns the words its [`Crossword`] borrows.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoadedCrossword {
+    pub words: Vec<String>,
+    pub clues: Vec<Option<Clue>>,
+    pub display: Vec<Option<String>>,
+    crosses: Vec<Option<CrossData>>,
+}
+
+impl LoadedCrossword {
+    /// Places the words in `order`, with the same checks used by the search.
+    pub(crate) fn place(
+        words: Vec<String>,
+        clues: Vec<Option<Clue>>,
+        display: Vec<Option<String>>,
+        crosses: Vec<Option<CrossData>>,
+        options: &SolverOptions,
+    ) -> Result<LoadedCrossword, LoadError> {
+        let mut placement_order: Vec<(usize, &CrossData)> = crosses
+            .iter()
+            .enumerate()
+            .filter_map(|(word_index, cross)| Some((word_index, cross.as_ref()?)))
+            .collect();
+        placement_order.sort_by_key(|(_, cross_data)| cross_data.order);
+
+        let mut crossword = crossword::initialise_empty(&words);
+        for (word_index, cross_data) in placement_order {
+            if !place_word(
+                word_index,
+                cross_data.row,
+                cross_data.start_point,
+                cross_data.direction,
+                options,
+                &mut crossword,
+            ) {
+                return Err(LoadError::Layout(format!(
+                    "'{}' cannot be placed there",
+                    letters::to_display(&words[word_index])
+                )));
+            }
+        }
+        if !crossword.is_connected() {
+            return Err(LoadError::Layout(
+                "the words do not form a single crossword".to_string(),
+            ));
+        }
+        if options.block_overlaps && !crossword.is_valid() {
+            return Err(LoadError::Layout(
+                "the words form invalid words where they touch".to_string(),
+            ));
+        }
+
+        let crosses = crossword
+            .words
+            .iter()
+            .map(|word| word.cross.clone())
+            .collect();
+
+        Ok(LoadedCrossword {
+            words,
+            clues,
+            display,
+            crosses,
+        })
+    }
+
+    /// The placed words in the order they were placed, as seeds for a new search.
+    pub fn seeds(&self) -> Vec<Seed> {
+        let mut placed: Vec<(&String, &CrossData)> = self
+            .words
+            .iter()
+            .zip(&self.crosses)
+            .filter_map(|(word, cross)| Some((word, cross.as_ref()?)))
+            .collect();
+        placed.sort_by_key(|(_, cross_data)| cross_data.order);
+
+        placed
+            .into_iter()
+            .map(|(word, cross_data)| Seed {
+                word: letters::to_display(word),
+                row: cross_data.row,
+                start_point: cross_data.start_point,
+                direction: cross_data.direction,
+            })
+            .collect()
+    }
+
+    pub fn crossword(&self) -> Crossword<'_> {
+        let mut crossword = crossword::initialise_empty(&self.words);
+        for (((word, clue), display), cross) in crossword
+            .words
+            .iter_mut()
+            .zip(&self.clues)
+            .zip(&self.display)
+            .zip(&self.crosses)
+        {
+            word.clue = clue.as_ref();
+            word.display = display.as_deref();
+            word.cross = cross.clone();
+        }
+
+        crossword
+    }
+}
+
+/// Saves a crossword as an object:
+///
+/// ```json
+/// {
+///   "words": [
+///     {
+///       "word": "hereby",
+///       "cross": {"row": 0, "start_point": 0, "direction": "across", "order": 0, "x": 0, "y": 0}
+///     },
+///     {"word": "unused", "clue": "Not needed", "enumeration": "(6)", "cross": null},
+///     {"word": "icecream", "display": "Ice cream", "cross": null}
+///   ],
+///   "bounding_box": {"x": 0, "y": 0, "width": 6, "height": 1}
+/// }
+/// ```
+///
+/// `row`, `start_point` and `order` are as in [`CrossData`], and `direction` is `"across"` or
+/// `"down"`. `clue` and `enumeration` are only written when given, and `display` only when the
+/// word was given in another form, e.g. with accents or spaces. `word` has one letter per cell,
+/// except that a digraph cell is written as its two letters, so loading needs the same
+/// [`Normalisation`](crate::letters::Normalisation) to split it into cells again. `x` and `y`
+/// are the coordinates of the first letter, with y increasing downwards. The bounding box gives
+/// the coordinates of the top left cell and the size of the grid.
+/// `x`, `y` and `bounding_box` are written for the benefit of other programs, and are ignored
+/// when loading.
+pub fn to_value(crossword: &Crossword) -> Value {
+    let words = crossword
+        .words
+        .iter()
+        .map(|word| {
+            let cross = match &word.cross {
+                Some(cross_data) => {
+                    let [x, y] = cross_data.get_position();
+                    Value::Object(vec![
+                        ("row".to_string(), Value::from(cross_data.row as i64)),
+                        (
+                            "start_point".to_string(),
+                            Value::from(cross_data.start_point as i64),
+                        ),
+                        (
+                            "direction".to_string(),
+                            Value::from(direction_name(cross_data.direction)),
+                        ),
+                        ("order".to_string(), Value::from(cross_data.order)),
+                        ("x".to_string(), Value::from(x as i64)),
+                        ("y".to_string(), Value::from(y as i64)),
+                    ])
+                }
+                None => Value::Null,
+            };
+
+            let mut members = vec![(
+                "word".to_string(),
+                Value::from(letters::to_display(word.word).as_str()),
+            )];
+            if let Some(display) = word.display {
+                members.push(("display".to_string(), Value::from(display)));
+            }
+            if let Some(clue) = word.clue {
+                members.push(("clue".to_string(), Value::from(clue.text.as_str())));
+                if let Some(enumeration) = &clue.enumeration {
+                    members.push(("enumeration".to_string(), Value::from(enumeration.as_str())));
+                }
+            }
+            members.push(("cross".to_string(), cross));
+
+            Value::Object(members)
+        })
+        .collect();
+
+    let bounding_box = if crossword.words.iter().any(|word| word.cross.is_some()) {
+        let [x, y] = crossword.get_origin();
+        let (width, height) = crossword.get_width_height();
+        Value::Object(vec![
+            ("x".to_string(), Value::from(x as i64)),
+            ("y".to_string(), Value::from(y as i64)),
+            ("width".to_string(), Value::from(width)),
+            ("height".to_string(), Value::from(height)),
+        ])
+    } else {
+        Value::Null
+    };
+
+    Value::Object(vec![
+        ("words".to_string(), Value::Array(words)),
+        ("bounding_box".to_string(), bounding_box),
+    ])
+}
+
+pub fn to_json(crossword: &Crossword) -> String {
+    to_value(crossword).to_pretty_string()
+}
+
+/// Saves a list of crosswords as a JSON array of crossword objects.
+pub fn to_json_list(crosswords: &[Crossword]) -> String {
+    Value::Array(crosswords.iter().map(to_value).collect()).to_pretty_string()
+}
+
+/// Loads a crossword object, or an array of them.
+///
+/// The words are placed in `order`, with the same checks used by the search, so a layout loads
+/// only if the solver could have produced it with these options.
+pub fn from_json(text: &str, options: &SolverOptions) -> Result<Vec<LoadedCrossword>, LoadError> {
+    let value = json::parse(text).map_err(LoadError::Syntax)?;
+
+    match value.as_array() {
+        Some(values) => values
+            .iter()
+            .map(|value| from_value(value, options))
+            .collect(),
+        None => Ok(vec![from_value(&value, options)?]),
+    }
+}
+
+pub fn from_value(value: &Value, options: &SolverOptions) -> Result<LoadedCrossword, LoadError> {
+    let schema = |message: &str| LoadError::Schema(message.to_string());
+
+    let word_values = value
+        .get("words")
+        .and_then(Value::as_array)
+        .ok_or_else(|| schema("expected an array of \"words\""))?;
+
+    let mut words = Vec::with_capacity(word_values.len());
+    let mut clues = Vec::with_capacity(word_values.len());
+    let mut display = Vec::with_capacity(word_values.len());
+    let mut crosses = Vec::with_capacity(word_values.len());
+    for word_value in word_values {
+        let word = word_value
+            .get("word")
+            .and_then(Value::as_str)
+            .filter(|word| !word.is_empty())
+            .ok_or_else(|| schema("every word needs a non-empty \"word\""))?;
+        words.push(letters::normalise(word, &options.normalisation));
+        display.push(
+            word_value
+                .get("display")
+                .and_then(Value::as_str)
+                .map(String::from),
+        );
+
+        let text = word_value.get("clue").and_then(Value::as_str);
+        let enumeration = word_value.get("enumeration").and_then(Value::as_str);
+        clues.push(match (text, enumeration) {
+            (None, None) => None,
+            (text, enumeration) => Some(Clue {
+                text: text.unwrap_or_default().to_string(),
+                enumeration: enumeration.map(String::from),
+            }),
+        });
+
+        crosses.push(match word_value.get("cross") {
+            None => None,
+            Some(cross) if cross.is_null() => None,
+            Some(cross) => Some(cross_from_value(cross, word)?),
+        });
+    }
+
+    LoadedCrossword::place(words, clues, display, crosses, options)
+}
+
+fn cross_from_value(value: &Value, word: &str) -> Result<CrossData, LoadError> {
+    let field = |name: &str| {
+        value
+            .get(name)
+            .and_then(Value::as_i64)
+            .ok_or_else(|| LoadError::Schema(format!("'{}' needs an integer \"{}\"", word, name)))
+    };
+    let coordinate = |name: &str| {
+        i32::try_from(field(name)?)
+            .map_err(|_| LoadError::Schema(format!("\"{}\" of '{}' is out of range", name, word)))
+    };
+
+    let direction = match value.get("direction").and_then(Value::as_str) {
+        Some("across") => Direction::Across,
+        Some("down") => Direction::Down,
+        _ => {
+            return Err(LoadError::Schema(format!(
+                "'{}' needs a \"direction\" of \"across\" or \"down\"",
+                word
+            )))
+        }
+    };
+
+    let cross_data = CrossData {
+        row: coordinate("row")?,
+        start_point: coordinate("start_point")?,
+        direction,
+        order: usize::try_from(field("order")?)
+            .map_err(|_| LoadError::Schema(format!("\"order\" of '{}' is negative", word)))?,
+    };
+    if !cross_data.fits(word) {
+        return Err(LoadError::Schema(format!("'{}' is out of range", word)));
+    }
+
+    Ok(cross_data)
+}
+
+fn direction_name(direction: Direction) -> &'static str {
+    match direction {
+        Direction::Across => "across",
+        Direction::Down => "down",
+    }
+}
+
+#[cfg(test)]
+#[path = "./tests_crossword_json.rs"]
+mod tests_crossword_json;
@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hasher};
+
+use crate::crossword::{CrossData, Crossword, Direction};
+
+/// A filled cell, and which directions the words through it run in, indexed by
+/// [`Direction::index`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Cell {
+    letter: char,
+    directions: [bool; 2],
+}
+
+/// The filled cells of a crossword by their `[x, y]` coordinates. It is kept up to date as words
+/// are placed and removed, so a placement can be checked cell by cell rather than against every
+/// word.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Grid {
+    cells: HashMap<[i32; 2], Cell, BuildHasherDefault<PositionHasher>>,
+    next_order: usize,
+    /// Where the first word placed starts. The key measures positions from here.
+    anchor: [i32; 2],
+    key: u128,
+}
+
+/// A multiply and rotate hash, as the default hasher's protection against chosen keys costs more
+/// than the rest of a lookup and is not needed for coordinates.
+#[derive(Default)]
+struct PositionHasher(u64);
+
+impl Hasher for PositionHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut word = [0; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.write_u64(u64::from_le_bytes(word));
+        }
+    }
+
+    fn write_u64(&mut self, word: u64) {
+        self.0 = (self.0.rotate_left(5) ^ word).wrapping_mul(0x517c_c1b7_2722_0a95);
+    }
+
+    fn write_usize(&mut self, word: usize) {
+        self.write_u64(word as u64);
+    }
+}
+
+impl Grid {
+    pub fn new(crossword: &Crossword) -> Grid {
+        let mut grid = Grid::default();
+
+        let mut placed: Vec<(usize, &str, &CrossData)> = crossword
+            .words
+            .iter()
+            .enumerate()
+            .filter_map(|(word_index, word)| Some((word_index, word.word, word.cross.as_ref()?)))
+            .collect();
+        placed.sort_by_key(|(_, _, cross_data)| cross_data.order);
+        for (word_index, word, cross_data) in placed {
+            grid.insert(word_index, word, cross_data);
+        }
+
+        grid
+    }
+
+    pub fn letter(&self, position: [i32; 2]) -> Option<char> {
+        self.cells.get(&position).map(|cell| cell.letter)
+    }
+
+    /// Whether a word running in `direction` fills the cell.
+    pub fn has_word(&self, position: [i32; 2], direction: Direction) -> bool {
+        self.cells
+            .get(&position)
+            .is_some_and(|cell| cell.directions[direction.index()])
+    }
+
+    /// Whether a word running in `direction` could still cross the cell. Not if a word already
+    /// runs that way through it, or through either cell beside it in that direction, as the new
+    /// word would have to run into it or end against it.
+    pub fn is_crossable(&self, position: [i32; 2], direction: Direction) -> bool {
+        let index = direction.index();
+        let mut before = position;
+        let mut after = position;
+        before[index] -= 1;
+        after[index] += 1;
+
+        [before, position, after]
+            .iter()
+            .all(|cell| !self.has_word(*cell, direction))
+    }
+
+    /// The order for the next word placed, as [`Crossword::get_next_order`].
+    pub fn next_order(&self) -> usize {
+        self.next_order
+    }
+
+    /// Which words are placed where, the same for two grids with the same words in the same
+    /// places whatever order they were placed in, and whichever cell the first one starts at.
+    /// Different placements share a key with a chance of about one in 2^128.
+    pub fn key(&self) -> u128 {
+        self.key
+    }
+
+    pub fn insert(&mut self, word_index: usize, word: &str, cross_data: &CrossData) {
+        let index = cross_data.direction.index();
+        let mut position = cross_data.get_position();
+        if self.cells.is_empty() {
+            self.anchor = position;
+        }
+        self.key ^= self.placement_key(word_index, cross_data);
+
+        for letter in word.chars() {
+            let cell = self.cells.entry(position).or_insert(Cell {
+                letter,
+                directions: [false; 2],
+            });
+            cell.directions[index] = true;
+            position[index] += 1;
+        }
+
+        self.next_order = self.next_order.max(cross_data.order + 1);
+    }
+
+    /// Removes a word placed with [`insert`]. Words are expected to be removed in the reverse of
+    /// the order they were placed in, so that the next order goes back to that of the removed word.
+    ///
+    /// [`insert`]: Grid::insert
+    pub fn remove(&mut self, word_index: usize, word: &str, cross_data: &CrossData) {
+        let index = cross_data.direction.index();
+        let mut position = cross_data.get_position();
+        self.key ^= self.placement_key(word_index, cross_data);
+
+        for _ in word.chars() {
+            if let Some(cell) = self.cells.get_mut(&position) {
+                cell.directions[index] = false;
+                if cell.directions == [false; 2] {
+                    self.cells.remove(&position);
+                }
+            }
+            position[index] += 1;
+        }
+
+        if cross_data.order + 1 == self.next_order {
+            self.next_order = cross_data.order;
+        }
+    }
+
+    /// A random looking value for one word in one place. The key of a grid combines these with
+    /// XOR, so that words can be added and removed in any order.
+    fn placement_key(&self, word_index: usize, cross_data: &CrossData) -> u128 {
+        let [x, y] = cross_data.get_position();
+        let [anchor_x, anchor_y] = self.anchor;
+        let position = u64::from((x - anchor_x) as u32) << 32 | u64::from((y - anchor_y) as u32);
+        let word = (word_index as u64) << 1 | cross_data.direction.index() as u64;
+
+        let low = mix(mix(word.wrapping_add(0x9e37_79b9_7f4a_7c15)) ^ position);
+        let high = mix(mix(!word) ^ position.rotate_left(16));
+        u128::from(high) << 64 | u128::from(low)
+    }
+}
+
+/// The SplitMix64 finaliser, which spreads every bit of the input across the output.
+fn mix(mut bits: u64) -> u64 {
+    bits = (bits ^ (bits >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    bits = (bits ^ (bits >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    bits ^ (bits >> 31)
+}
+
+#[cfg(test)]
+#[path = "./tests_grid.rs"]
+mod tests_grid;
@@ -0,0 +1,265 @@
+use std::collections::HashMap;
+
+use crate::clues::{self, Clue};
+use crate::crossword::{CrossData, Crossword, Direction, WordCross};
+use crate::crossword_json::{LoadError, LoadedCrossword};
+use crate::json::{self, Value};
+use crate::letters;
+use crate::solver::SolverOptions;
+
+const BLOCK: &str = "#";
+
+/// Saves a crossword as an ipuz crossword, with the grid cropped to its bounding box. Empty cells
+/// are blocks, and the clue lists follow the usual numbering. Words without a clue are given an
+/// empty one, so every word still appears in the lists.
+pub fn to_ipuz(crossword: &Crossword) -> String {
+    let letters = crossword.get_letters();
+    let numbering = clues::numbering(crossword);
+    let (x_low, y_low, width, height) = if letters.is_empty() {
+        (0, 0, 0, 0)
+    } else {
+        let [x_low, y_low] = crossword.get_origin();
+        let (width, height) = crossword.get_width_height();
+        (x_low, y_low, width, height)
+    };
+
+    let mut puzzle = Vec::with_capacity(height);
+    let mut solution = Vec::with_capacity(height);
+    for y in y_low..y_low + height as i32 {
+        let mut puzzle_row = Vec::with_capacity(width);
+        let mut solution_row = Vec::with_capacity(width);
+        for x in x_low..x_low + width as i32 {
+            match letters.get(&[x, y]) {
+                Some(letter) => {
+                    puzzle_row.push(Value::from(
+                        numbering.numbers.get(&[x, y]).copied().unwrap_or(0),
+                    ));
+                    solution_row.push(Value::from(
+                        letters::get_cell_text(*letter).to_uppercase().as_str(),
+                    ));
+                }
+                None => {
+                    puzzle_row.push(Value::from(BLOCK));
+                    solution_row.push(Value::from(BLOCK));
+                }
+            }
+        }
+        puzzle.push(Value::Array(puzzle_row));
+        solution.push(Value::Array(solution_row));
+    }
+
+    let clue_list = |entries: &[clues::Entry]| {
+        let values = entries
+            .iter()
+            .map(|entry| {
+                let clue = crossword.words[entry.word_index].clue;
+                let text = clue.map_or("", |clue| clue.text.as_str());
+                match clue.and_then(|clue| clue.enumeration.as_ref()) {
+                    Some(enumeration) => Value::Object(vec![
+                        ("number".to_string(), Value::from(entry.number)),
+                        ("clue".to_string(), Value::from(text)),
+                        ("enumeration".to_string(), Value::from(enumeration.as_str())),
+                    ]),
+                    None => Value::Array(vec![Value::from(entry.number), Value::from(text)]),
+                }
+            })
+            .collect();
+        Value::Array(values)
+    };
+
+    Value::Object(vec![
+        ("version".to_string(), Value::from("http://ipuz.org/v2")),
+        (
+            "kind".to_string(),
+            Value::Array(vec![Value::from("http://ipuz.org/crossword#1")]),
+        ),
+        (
+            "dimensions".to_string(),
+            Value::Object(vec![
+                ("width".to_string(), Value::from(width)),
+                ("height".to_string(), Value::from(height)),
+            ]),
+        ),
+        ("block".to_string(), Value::from(BLOCK)),
+        ("empty".to_string(), Value::from(0_usize)),
+        ("puzzle".to_string(), Value::Array(puzzle)),
+        ("solution".to_string(), Value::Array(solution)),
+        (
+            "clues".to_string(),
+            Value::Object(vec![
+                ("Across".to_string(), clue_list(&numbering.across)),
+                ("Down".to_string(), clue_list(&numbering.down)),
+            ]),
+        ),
+    ])
+    .to_pretty_string()
+}
+
+/// Reads the words of an ipuz crossword from its solution grid, with their clues when given.
+/// Every run of two or more cells is a word, and the top left cell is at (0, 0). A cell holds one
+/// letter, or one of the [`letters::DIGRAPHS`]. The words are placed with the same checks as the
+/// search, so the grid must be one the solver could produce with these options.
+pub fn from_ipuz(text: &str, options: &SolverOptions) -> Result<LoadedCrossword, LoadError> {
+    let value = json::parse(text).map_err(LoadError::Syntax)?;
+    let schema = |message: &str| LoadError::Schema(message.to_string());
+
+    let is_crossword = value
+        .get("kind")
+        .and_then(Value::as_array)
+        .is_some_and(|kinds| {
+            kinds.iter().any(|kind| {
+                kind.as_str()
+                    .is_some_and(|kind| kind.starts_with("http://ipuz.org/crossword"))
+            })
+        });
+    if !is_crossword {
+        return Err(schema("not an ipuz crossword"));
+    }
+    let block = value.get("block").and_then(Value::as_str).unwrap_or(BLOCK);
+
+    let rows = value
+        .get("solution")
+        .and_then(Value::as_array)
+        .ok_or_else(|| schema("expected a \"solution\" grid"))?;
+    let mut cells: HashMap<[i32; 2], char> = HashMap::new();
+    for (y, row) in rows.iter().enumerate() {
+        let row = row
+            .as_array()
+            .ok_or_else(|| schema("every row of the \"solution\" must be an array"))?;
+        for (x, cell) in row.iter().enumerate() {
+            let letter = match cell {
+                Value::String(letter) => Some(letter.as_str()),
+                Value::Object(_) => cell.get("value").and_then(Value::as_str),
+                _ => None,
+            };
+            if let Some(letter) = letter.filter(|letter| !letter.is_empty() && *letter != block) {
+                let cell = get_cell(letter, options).ok_or_else(|| {
+                    LoadError::Schema(format!("'{}' does not fit a cell", letter))
+                })?;
+                cells.insert([x as i32, y as i32], cell);
+            }
+        }
+    }
+
+    let mut words = Vec::new();
+    let mut crosses = Vec::new();
+    let mut positions: Vec<&[i32; 2]> = cells.keys().collect();
+    positions.sort_by_key(|[x, y]| (*y, *x));
+    for direction in [Direction::Across, Direction::Down] {
+        let index = direction.index();
+        for position in &positions {
+            let mut previous = **position;
+            previous[index] -= 1;
+            if cells.contains_key(&previous) {
+                continue;
+            }
+
+            let mut word = String::new();
+            let mut next = **position;
+            while let Some(cell) = cells.get(&next) {
+                word.push(*cell);
+                next[index] += 1;
+            }
+            if next[index] - position[index] < 2 {
+                continue;
+            }
+
+            let [x, y] = **position;
+            let (row, start_point) = match direction {
+                Direction::Across => (y, x),
+                Direction::Down => (x, y),
+            };
+            crosses.push(Some(CrossData {
+                row,
+                start_point,
+                direction,
+                order: 0,
+            }));
+            words.push(word);
+        }
+    }
+
+    let mut crossword = Crossword {
+        words: words
+            .iter()
+            .zip(&crosses)
+            .map(|(word, cross)| WordCross {
+                word,
+                clue: None,
+                display: None,
+                cross: cross.clone(),
+            })
+            .collect(),
+    };
+    // The grid does not say which word was placed first, so each word is placed after one it
+    // crosses and after any word joining it to a word beside it, as the search would have.
+    crossword.set_crossing_order(0);
+
+    let mut clues = vec![None; words.len()];
+    if let Some(clue_lists) = value.get("clues") {
+        let numbering = clues::numbering(&crossword);
+
+        for (name, entries) in [("Across", &numbering.across), ("Down", &numbering.down)] {
+            let Some(clue_values) = clue_lists.get(name).and_then(Value::as_array) else {
+                continue;
+            };
+            for clue_value in clue_values {
+                let Some((number, clue)) = clue_from_value(clue_value) else {
+                    continue;
+                };
+                if let Some(entry) = entries.iter().find(|entry| entry.number == number) {
+                    clues[entry.word_index] = Some(clue);
+                }
+            }
+        }
+    }
+
+    let crosses = crossword.words.into_iter().map(|word| word.cross).collect();
+    let display = vec![None; words.len()];
+    LoadedCrossword::place(words, clues, display, crosses, options)
+}
+
+/// The cell for the text of a solution square: a letter normalised as in the search, or a digraph.
+fn get_cell(letter: &str, options: &SolverOptions) -> Option<char> {
+    let normalised = letters::normalise(letter, &options.normalisation);
+    let mut cells = normalised.chars();
+    match (cells.next(), cells.next()) {
+        (Some(cell), None) => Some(cell),
+        _ => letters::get_digraph_cell(&letter.to_lowercase()),
+    }
+}
+
+/// Reads a clue given as `[number, "clue"]` or as an object with `number`, `clue` and optionally
+/// `enumeration`. Empty clues are skipped.
+fn clue_from_value(value: &Value) -> Option<(usize, Clue)> {
+    let (number, text, enumeration) = match value {
+        Value::Array(values) => (values.first()?, values.get(1)?, None),
+        Value::Object(_) => (
+            value.get("number")?,
+            value.get("clue")?,
+            value.get("enumeration"),
+        ),
+        _ => return None,
+    };
+    let number = match number {
+        Value::String(number) => number.parse().ok()?,
+        number => usize::try_from(number.as_i64()?).ok()?,
+    };
+    let text = text.as_str()?;
+    let enumeration = enumeration.and_then(Value::as_str);
+    if text.is_empty() && enumeration.is_none() {
+        return None;
+    }
+
+    Some((
+        number,
+        Clue {
+            text: text.to_string(),
+            enumeration: enumeration.map(String::from),
+        },
+    ))
+}
+
+#[cfg(test)]
+#[path = "./tests_ipuz.rs"]
+mod tests_ipuz;
@@ -0,0 +1,390 @@
+use std::fmt::Write;
+
+/// How deeply arrays and objects may be nested, so that a hostile file gives an error rather than
+/// overflowing the stack.
+const MAX_DEPTH: usize = 128;
+
+/// A JSON value. Object members keep the order they were written in.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(members) => members
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            // Whole numbers from -2^63 up to, but not including, 2^63 convert exactly.
+            Value::Number(number)
+                if number.fract() == 0.0
+                    && *number >= i64::MIN as f64
+                    && *number < i64::MAX as f64 =>
+            {
+                Some(*number as i64)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        *self == Value::Null
+    }
+
+    pub fn to_pretty_string(&self) -> String {
+        let mut output = String::new();
+        write_value(self, 0, &mut output);
+        output.push('\n');
+        output
+    }
+}
+
+impl From<&str> for Value {
+    fn from(string: &str) -> Value {
+        Value::String(string.to_string())
+    }
+}
+
+impl From<i64> for Value {
+    fn from(number: i64) -> Value {
+        Value::Number(number as f64)
+    }
+}
+
+impl From<usize> for Value {
+    fn from(number: usize) -> Value {
+        Value::Number(number as f64)
+    }
+}
+
+fn write_value(value: &Value, indent: usize, output: &mut String) {
+    match value {
+        Value::Null => output.push_str("null"),
+        Value::Bool(boolean) => output.push_str(if *boolean { "true" } else { "false" }),
+        Value::Number(number) => write!(output, "{}", number).unwrap(),
+        Value::String(string) => write_string(string, output),
+        Value::Array(values) if values.is_empty() => output.push_str("[]"),
+        Value::Array(values) => {
+            output.push('[');
+            for (index, value) in values.iter().enumerate() {
+                if index > 0 {
+                    output.push(',');
+                }
+                write_newline(indent + 1, output);
+                write_value(value, indent + 1, output);
+            }
+            write_newline(indent, output);
+            output.push(']');
+        }
+        Value::Object(members) if members.is_empty() => output.push_str("{}"),
+        Value::Object(members) => {
+            output.push('{');
+            for (index, (name, value)) in members.iter().enumerate() {
+                if index > 0 {
+                    output.push(',');
+                }
+                write_newline(indent + 1, output);
+                write_string(name, output);
+                output.push_str(": ");
+                write_value(value, indent + 1, output);
+            }
+            write_newline(indent, output);
+            output.push('}');
+        }
+    }
+}
+
+fn write_newline(indent: usize, output: &mut String) {
+    output.push('\n');
+    for _ in 0..indent {
+        output.push_str("  ");
+    }
+}
+
+fn write_string(string: &str, output: &mut String) {
+    output.push('"');
+    for character in string.chars() {
+        match character {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(output, "\\u{:04x}", c as u32).unwrap(),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+}
+
+pub fn parse(text: &str) -> Result<Value, String> {
+    let mut parser = Parser {
+        chars: text.chars().collect(),
+        position: 0,
+        depth: 0,
+    };
+
+    let value = parser.parse_value()?;
+    parser.skip_whitespace();
+    if parser.position < parser.chars.len() {
+        return Err(parser.error("unexpected trailing characters"));
+    }
+
+    Ok(value)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn error(&self, message: &str) -> String {
+        format!("{} at character {}", message, self.position)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let next = self.peek();
+        self.position += 1;
+        next
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.position += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+        if self.peek() == Some(expected) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", expected)))
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Value, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{' | '[') if self.depth == MAX_DEPTH => Err(self.error("nested too deeply")),
+            Some('{') => self.parse_nested(Parser::parse_object),
+            Some('[') => self.parse_nested(Parser::parse_array),
+            Some('"') => Ok(Value::String(self.parse_string()?)),
+            Some('t') => self.parse_literal("true", Value::Bool(true)),
+            Some('f') => self.parse_literal("false", Value::Bool(false)),
+            Some('n') => self.parse_literal("null", Value::Null),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.parse_number(),
+            _ => Err(self.error("expected a value")),
+        }
+    }
+
+    fn parse_nested(
+        &mut self,
+        parse: fn(&mut Parser) -> Result<Value, String>,
+    ) -> Result<Value, String> {
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn parse_literal(&mut self, literal: &str, value: Value) -> Result<Value, String> {
+        for expected in literal.chars() {
+            if self.next() != Some(expected) {
+                return Err(self.error(&format!("expected '{}'", literal)));
+            }
+        }
+        Ok(value)
+    }
+
+    fn parse_number(&mut self) -> Result<Value, String> {
+        let start = self.position;
+        self.skip_char('-');
+        // Rust accepts numbers that JSON does not, such as "01", "1." and ".5", so the grammar is
+        // checked here before the text is converted.
+        if !self.skip_char('0') && self.skip_digits() == 0 {
+            return Err(self.error("invalid number"));
+        }
+        if self.skip_char('.') && self.skip_digits() == 0 {
+            return Err(self.error("invalid number"));
+        }
+        if self.skip_char('e') || self.skip_char('E') {
+            if !self.skip_char('+') {
+                self.skip_char('-');
+            }
+            if self.skip_digits() == 0 {
+                return Err(self.error("invalid number"));
+            }
+        }
+
+        let number: String = self.chars[start..self.position].iter().collect();
+        match number.parse::<f64>() {
+            Ok(number) if number.is_finite() => Ok(Value::Number(number)),
+            _ => Err(self.error("number out of range")),
+        }
+    }
+
+    fn skip_char(&mut self, expected: char) -> bool {
+        let found = self.peek() == Some(expected);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn skip_digits(&mut self) -> usize {
+        let start = self.position;
+        while matches!(self.peek(), Some(c) if c.is_ascii_digit()) {
+            self.position += 1;
+        }
+        self.position - start
+    }
+
+    fn parse_string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+
+        let mut string = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(string),
+                Some('\\') => match self.next() {
+                    Some('"') => string.push('"'),
+                    Some('\\') => string.push('\\'),
+                    Some('/') => string.push('/'),
+                    Some('b') => string.push('\u{8}'),
+                    Some('f') => string.push('\u{c}'),
+                    Some('n') => string.push('\n'),
+                    Some('r') => string.push('\r'),
+                    Some('t') => string.push('\t'),
+                    Some('u') => string.push(self.parse_unicode_escape()?),
+                    _ => return Err(self.error("invalid escape")),
+                },
+                Some(c) if (c as u32) < 0x20 => {
+                    return Err(self.error("control character in string"))
+                }
+                Some(c) => string.push(c),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    fn parse_unicode_escape(&mut self) -> Result<char, String> {
+        let high = self.parse_hex4()?;
+        let code = if (0xd800..0xdc00).contains(&high) {
+            if self.next() != Some('\\') || self.next() != Some('u') {
+                return Err(self.error("unpaired surrogate"));
+            }
+            let low = self.parse_hex4()?;
+            if !(0xdc00..0xe000).contains(&low) {
+                return Err(self.error("unpaired surrogate"));
+            }
+            0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+        } else {
+            high
+        };
+
+        char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, String> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let digit = self
+                .next()
+                .and_then(|c| c.to_digit(16))
+                .ok_or_else(|| self.error("invalid unicode escape"))?;
+            code = code * 16 + digit;
+        }
+        Ok(code)
+    }
+
+    fn parse_array(&mut self) -> Result<Value, String> {
+        self.expect('[')?;
+
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.position += 1;
+            return Ok(Value::Array(values));
+        }
+
+        loop {
+            values.push(self.parse_value()?);
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => {}
+                Some(']') => return Ok(Value::Array(values)),
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<Value, String> {
+        self.expect('{')?;
+
+        let mut members = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.position += 1;
+            return Ok(Value::Object(members));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let name = self.parse_string()?;
+            self.expect(':')?;
+            members.push((name, self.parse_value()?));
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => {}
+                Some('}') => return Ok(Value::Object(members)),
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "./tests_json.rs"]
+mod tests_json;
@@ -1,5 +1,86 @@
 use std::collections::HashMap;
 
+/// Digraphs which can fill a single cell, as in Welsh, Dutch, Spanish, Hungarian and Croatian.
+/// Within a word each one is stored as a private use character, so that a cell is still one
+/// `char`. New digraphs must be added at the end, as saved crosswords refer to them by position.
+pub const DIGRAPHS: &[&str] = &[
+    "ch", "dd", "ff", "ng", "ll", "ph", "rh", "th", "ij", "rr", "cs", "dz", "dzs", "gy", "ly",
+    "ny", "sz", "ty", "zs", "dž", "lj", "nj",
+];
+const FIRST_DIGRAPH_CELL: u32 = 0xE000;
+pub(crate) const SEPARATORS: &[char] = &[' ', '-', '\'', '’'];
+const COMBINING_MARKS: std::ops::RangeInclusive<char> = '\u{0300}'..='\u{036f}';
+
+/// For each combining accent, the lowercase Latin, Greek and Cyrillic letters which Unicode
+/// composes with it, and the letters they make, in the same order.
+const COMPOSITIONS: &[(char, &str, &str)] = &[
+    (
+        '\u{300}',
+        "aeiouünеиēōwâăêôơưyἀἁἐἑἠἡἰἱὀὁὐὑὠὡαεηιουωϊϋ",
+        "àèìòùǜǹѐѝḕṑẁầằềồờừỳἂἃἒἓἢἣἲἳὂὃὒὓὢὣὰὲὴὶὸὺὼῒῢ",
+    ),
+    (
+        '\u{301}',
+        "aeiouyclnrszügåæøϊαεηιϋουωϒгкçēïkmõōpũwâăêôơưἀἁἐἑἠἡἰἱὀὁὐὑὠὡ",
+        "áéíóúýćĺńŕśźǘǵǻǽǿΐάέήίΰόύώϓѓќḉḗḯḱḿṍṓṕṹẃấắếốớứἄἅἔἕἤἥἴἵὄὅὔὕὤὥ",
+    ),
+    ('\u{302}', "aeioucghjswyzạẹọ", "âêîôûĉĝĥĵŝŵŷẑậệộ"),
+    ('\u{303}', "anoiuvâăeêôơưy", "ãñõĩũṽẫẵẽễỗỡữỹ"),
+    (
+        '\u{304}',
+        "aeiouüäȧæǫöõȯyиуgḷṛαιυ",
+        "āēīōūǖǟǡǣǭȫȭȱȳӣӯḡḹṝᾱῑῡ",
+    ),
+    ('\u{306}', "aegiouиужаеȩạαιυ", "ăĕğĭŏŭйўӂӑӗḝặᾰῐῠ"),
+    (
+        '\u{307}',
+        "cegzaobdfhmnprsśšṣtwxyſ",
+        "ċėġżȧȯḃḋḟḣṁṅṗṙṡṥṧṩṫẇẋẏẛ",
+    ),
+    (
+        '\u{308}',
+        "aeiouyιυϒеіаәжзиоөэучыhõūwxt",
+        "äëïöüÿϊϋϔёїӓӛӝӟӥӧӫӭӱӵӹḧṏṻẅẍẗ",
+    ),
+    ('\u{309}', "aâăeêioôơuưy", "ảẩẳẻểỉỏổởủửỷ"),
+    ('\u{30a}', "auwy", "åůẘẙ"),
+    ('\u{30b}', "ouу", "őűӳ"),
+    ('\u{30c}', "cdelnrstzaiouügkʒjh", "čďěľňřšťžǎǐǒǔǚǧǩǯǰȟ"),
+    ('\u{30f}', "aeioruѵ", "ȁȅȉȍȑȕѷ"),
+    ('\u{311}', "aeioru", "ȃȇȋȏȓȗ"),
+    ('\u{313}', "αεηιουωρ", "ἀἐἠἰὀὐὠῤ"),
+    ('\u{314}', "αεηιουωρ", "ἁἑἡἱὁὑὡῥ"),
+    ('\u{31b}', "ou", "ơư"),
+    ('\u{323}', "bdhklmnrstvwzaeioơuưy", "ḅḍḥḳḷṃṇṛṣṭṿẉẓạẹịọợụựỵ"),
+    ('\u{324}', "u", "ṳ"),
+    ('\u{325}', "a", "ḁ"),
+    ('\u{326}', "st", "șț"),
+    ('\u{327}', "cgklnrstedh", "çģķļņŗşţȩḑḩ"),
+    ('\u{328}', "aeiuo", "ąęįųǫ"),
+    ('\u{32d}', "delntu", "ḓḙḽṋṱṷ"),
+    ('\u{32e}', "h", "ḫ"),
+    ('\u{330}', "eiu", "ḛḭṵ"),
+    ('\u{331}', "bdklnrtzh", "ḇḏḵḻṉṟṯẕẖ"),
+    ('\u{342}', "ἀἁἠἡἰἱὐὑὠὡαηιϊυϋω", "ἆἇἦἧἶἷὖὗὦὧᾶῆῖῗῦῧῶ"),
+    (
+        '\u{345}',
+        "ἀἁἂἃἄἅἆἇἠἡἢἣἤἥἦἧὠὡὢὣὤὥὦὧὰαάᾶὴηήῆὼωώῶ",
+        "ᾀᾁᾂᾃᾄᾅᾆᾇᾐᾑᾒᾓᾔᾕᾖᾗᾠᾡᾢᾣᾤᾥᾦᾧᾲᾳᾴᾷῂῃῄῇῲῳῴῷ",
+    ),
+];
+
+/// How words are turned into the letters of their cells. The default only lowercases.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Normalisation {
+    /// Match letters without their accents, so that "é" crosses "e".
+    pub strip_diacritics: bool,
+    /// Digraphs from [`DIGRAPHS`] which take a single cell, such as "ll" and "ch" in Welsh.
+    /// Others are ignored.
+    pub digraphs: Vec<String>,
+    /// Leave out spaces, hyphens and apostrophes, so that "ice cream" fills 8 cells.
+    pub drop_separators: bool,
+}
+
 pub struct WordAndLetter<'a> {
     pub word_index: usize,
     pub word: &'a str,
@@ -9,10 +90,146 @@ pub struct WordAndLetter<'a> {
 }
 
 pub fn to_lowercase(words: &[&str]) -> Vec<String> {
-    return words.iter().map(|x| x.to_lowercase()).collect();
+    words.iter().map(|x| x.to_lowercase()).collect()
+}
+
+/// Turns a word into its cells, one `char` each. Digraphs are matched before accents are stripped,
+/// with the longest first, so "dzs" is one cell rather than "dz" followed by "s".
+pub fn normalise(word: &str, normalisation: &Normalisation) -> String {
+    let mut digraphs: Vec<(&str, char)> = normalisation
+        .digraphs
+        .iter()
+        .filter_map(|digraph| Some((digraph.as_str(), get_digraph_cell(digraph)?)))
+        .collect();
+    digraphs.sort_by_key(|(digraph, _)| std::cmp::Reverse(digraph.len()));
+
+    let lowercase = compose_accents(&word.to_lowercase());
+    let mut rest = lowercase.as_str();
+    let mut cells = String::with_capacity(rest.len());
+    while let Some(letter) = rest.chars().next() {
+        if let Some((digraph, cell)) = digraphs
+            .iter()
+            .find(|(digraph, _)| rest.starts_with(digraph))
+        {
+            cells.push(*cell);
+            rest = &rest[digraph.len()..];
+            continue;
+        }
+
+        rest = &rest[letter.len_utf8()..];
+        if normalisation.drop_separators && SEPARATORS.contains(&letter) {
+            continue;
+        }
+        // Accents written as combining marks after their letter are dropped along with them.
+        if normalisation.strip_diacritics && COMBINING_MARKS.contains(&letter) {
+            continue;
+        }
+        cells.push(if normalisation.strip_diacritics {
+            strip_diacritic(letter)
+        } else {
+            letter
+        });
+    }
+
+    cells
+}
+
+/// Puts each combining accent on the letter before it, where Unicode has the two as one letter, so
+/// that a word typed with separate accents takes the same cells as one typed with composed letters.
+fn compose_accents(word: &str) -> String {
+    let mut composed = String::with_capacity(word.len());
+    let mut previous: Option<char> = None;
+    for letter in word.chars() {
+        match previous.and_then(|previous| compose(previous, letter)) {
+            Some(letter) => previous = Some(letter),
+            None => {
+                composed.extend(previous);
+                previous = Some(letter);
+            }
+        }
+    }
+    composed.extend(previous);
+
+    composed
+}
+
+/// The letter with the accent on it, from [`COMPOSITIONS`].
+fn compose(letter: char, accent: char) -> Option<char> {
+    let (_, letters, composed) = COMPOSITIONS.iter().find(|(mark, ..)| *mark == accent)?;
+    let index = letters.chars().position(|base| base == letter)?;
+    composed.chars().nth(index)
+}
+
+/// The letter under the accent of a letter from [`COMPOSITIONS`].
+fn decompose(letter: char) -> Option<char> {
+    COMPOSITIONS.iter().find_map(|(_, letters, composed)| {
+        let index = composed.chars().position(|known| known == letter)?;
+        letters.chars().nth(index)
+    })
+}
+
+/// The cell which holds a digraph, if it is one of [`DIGRAPHS`].
+pub fn get_digraph_cell(digraph: &str) -> Option<char> {
+    let index = DIGRAPHS.iter().position(|known| *known == digraph)?;
+    char::from_u32(FIRST_DIGRAPH_CELL + index as u32)
+}
+
+/// The text of a cell: its letter, or both letters of a digraph.
+pub fn get_cell_text(cell: char) -> String {
+    let digraph = (cell as u32)
+        .checked_sub(FIRST_DIGRAPH_CELL)
+        .and_then(|index| DIGRAPHS.get(index as usize));
+    match digraph {
+        Some(digraph) => digraph.to_string(),
+        None => cell.to_string(),
+    }
+}
+
+/// Writes out the cells of a word as text, with digraphs as their letters.
+pub fn to_display(word: &str) -> String {
+    word.chars().map(get_cell_text).collect()
+}
+
+/// The letter without its accent, for the lowercase Latin, Greek and Cyrillic letters which have
+/// one, or several. Letters such as "ß", "æ" and "ø" are kept, as they are letters in their own
+/// right.
+fn strip_diacritic(letter: char) -> char {
+    match letter {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => 'a',
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => 'c',
+        'ď' | 'đ' => 'd',
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => 'e',
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => 'g',
+        'ĥ' | 'ħ' => 'h',
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' => 'i',
+        'ĵ' => 'j',
+        'ķ' => 'k',
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => 'l',
+        'ñ' | 'ń' | 'ņ' | 'ň' => 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ō' | 'ŏ' | 'ő' => 'o',
+        'ŕ' | 'ŗ' | 'ř' => 'r',
+        'ś' | 'ŝ' | 'ş' | 'š' | 'ș' => 's',
+        'ţ' | 'ť' | 'ŧ' | 'ț' => 't',
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => 'u',
+        'ŵ' | 'ẁ' | 'ẃ' | 'ẅ' => 'w',
+        'ý' | 'ÿ' | 'ŷ' | 'ỳ' => 'y',
+        'ź' | 'ż' | 'ž' => 'z',
+        'ά' => 'α',
+        'έ' => 'ε',
+        'ή' => 'η',
+        'ί' | 'ϊ' | 'ΐ' => 'ι',
+        'ό' => 'ο',
+        'ύ' | 'ϋ' | 'ΰ' => 'υ',
+        'ώ' => 'ω',
+        'ё' => 'е',
+        'й' => 'и',
+        'ї' => 'і',
+        'ў' => 'у',
+        _ => decompose(letter).map_or(letter, strip_diacritic),
+    }
 }
 
-pub fn get_map(words: &[String]) -> HashMap<char, Vec<WordAndLetter>> {
+pub fn get_map(words: &[String]) -> HashMap<char, Vec<WordAndLetter<'_>>> {
     let mut letter_map: HashMap<char, Vec<WordAndLetter>> = HashMap::new();
 
     for (word_index, word) in words.iter().enumerate() {
@@ -22,7 +239,7 @@ pub fn get_map(words: &[String]) -> HashMap<char, Vec<WordAndLetter>> {
                 word,
                 letter,
                 letter_index,
-                n_letters_after: word.len() - letter_index - 1,
+                n_letters_after: word.chars().count() - letter_index - 1,
             };
 
             let words_with_letter = letter_map.entry(letter).or_default();
@@ -35,4 +252,10 @@ pub fn get_map(words: &[String]) -> HashMap<char, Vec<WordAndLetter>> {
 
 #[cfg(test)]
 #[path = "./tests_letters.rs"]
+#[allow(
+    clippy::needless_borrow,
+    clippy::redundant_pattern_matching,
+    clippy::assertions_on_constants,
+    clippy::bool_assert_comparison
+)]
 mod tests_letters;
@@ -0,0 +1,27 @@
+pub mod clues;
+pub mod crossword;
+pub mod crossword_json;
+pub(crate) mod grid;
+pub mod ipuz;
+pub mod json;
+pub mod letters;
+pub mod observer;
+pub(crate) mod options;
+pub mod pareto;
+pub mod progress;
+pub mod puz;
+pub mod scorer;
+mod solver;
+pub mod svg;
+pub(crate) mod transpositions;
+
+#[cfg(test)]
+mod tests_helpers;
+
+pub use crate::crossword::{CanonicalForm, CrossData, Crossword, Direction, WordCross};
+pub use crate::observer::Observer;
+pub use crate::scorer::Scorer;
+pub use crate::solver::{
+    CancelToken, Heuristic, MaxSize, Seed, SeedError, Selection, Solution, Solver, SolverOptions,
+    DEFAULT_TRANSPOSITION_LIMIT,
+};
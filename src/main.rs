@@ -1,23 +1,172 @@
-mod crossword;
-mod letters;
-mod options;
-use crate::options::options_compare;
+use std::env;
+use std::process::ExitCode;
+use std::sync::Arc;
+
+use crossword_compressor::observer::Observer;
+use crossword_compressor::pareto::Objectives;
+use crossword_compressor::puz::{self, PuzInfo};
+use crossword_compressor::{clues, ipuz, letters, svg};
+use crossword_compressor::{Crossword, Selection, Solver};
+
+mod cli;
 mod output;
 
-fn main() {
-    let words_input = vec!["hereby", "exist", "words", "for", "sample", "output"];
+/// Prints the options as the search finds them.
+#[derive(Debug)]
+struct Printer;
+
+impl Observer for Printer {
+    fn found(&self, crossword: &Crossword) {
+        crossword.print();
+    }
+
+    fn improved(&self, _crossword: &Crossword) {
+        output::clear_message("Selection improved!");
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
 
-    let words = letters::to_lowercase(&words_input);
+    let arguments = match cli::parse(&args) {
+        Ok(arguments) => arguments,
+        Err(message) => return bad_input(&message),
+    };
 
-    let letter_map = letters::get_map(&words);
+    if arguments.help {
+        println!("{}", cli::USAGE);
+        return ExitCode::from(cli::EXIT_SOLUTION_FOUND);
+    }
+
+    if let Some(path) = &arguments.load_file {
+        return load(path, &arguments);
+    }
 
-    let mut crossword = crossword::initialise(&words);
+    let (words_input, clues) =
+        match cli::read_words(&arguments.source, &arguments.options.normalisation) {
+            Ok(words_and_clues) => words_and_clues,
+            Err(message) => return bad_input(&message),
+        };
 
-    let mut best_options = vec![];
-    options_compare(&letter_map, &mut crossword, &mut best_options);
+    let seeds = match cli::read_seeds(&arguments) {
+        Ok(seeds) => seeds,
+        Err(message) => return bad_input(&message),
+    };
+
+    let mut options = arguments.options.clone();
+    if arguments.verbosity != cli::Verbosity::Quiet {
+        options.observer = Some(Arc::new(Printer));
+    }
+    let solver = Solver::new(&words_input, options).with_clues(clues);
+    let solver = match solver.with_seeds(&seeds) {
+        Ok(solver) => solver,
+        Err(error) => return bad_input(&error.to_string()),
+    };
+    let solution = solver.solve();
+    let best_options = solution.crosswords;
 
-    output::clear_message(&format!("{} excellent options", best_options.len()));
+    if arguments.verbosity != cli::Verbosity::Quiet {
+        if !solution.exhaustive {
+            output::clear_message("Search stopped early. Showing the best options found so far.");
+        }
+        output::clear_message(&format!("{} excellent options", best_options.len()));
+    }
+    save(&arguments, &best_options);
     for crossword in &best_options {
-        crossword.print();
+        if let Selection::ParetoFront { crossings } = solver.options().selection {
+            let objectives = Objectives::new(crossword, crossings);
+            print!("{} x {}", objectives.width, objectives.height);
+            if crossings {
+                print!(", {} crossings", objectives.crossings);
+            }
+            println!();
+        }
+        print_crossword(crossword);
+    }
+
+    if best_options.is_empty() && !solution.exhaustive {
+        // A crossword may still exist, so the search was too short rather than the words unfit.
+        eprintln!("Search stopped before any crossword was found");
+        ExitCode::from(cli::EXIT_STOPPED_EARLY)
+    } else if best_options.is_empty() {
+        if let Some(max_size) = &solver.options().max_size {
+            eprintln!(
+                "No crossword fits within {}x{}",
+                max_size.width, max_size.height
+            );
+            for word in solver.words() {
+                if !max_size.fits(word.chars().count(), 1) {
+                    eprintln!("'{}' is too long to fit", letters::to_display(word));
+                }
+            }
+        }
+        ExitCode::from(cli::EXIT_NO_CROSSWORD)
+    } else {
+        ExitCode::from(cli::EXIT_SOLUTION_FOUND)
     }
 }
+
+fn load(path: &str, arguments: &cli::Arguments) -> ExitCode {
+    let loaded = match cli::read_crosswords(path, &arguments.options) {
+        Ok(loaded) => loaded,
+        Err(message) => {
+            // The arguments were fine, so the usage would only bury the error.
+            eprintln!("error: {}", message);
+            return ExitCode::from(cli::EXIT_BAD_INPUT);
+        }
+    };
+    let crosswords: Vec<_> = loaded.iter().map(|loaded| loaded.crossword()).collect();
+
+    save(arguments, &crosswords);
+    for crossword in &crosswords {
+        print_crossword(crossword);
+    }
+
+    if crosswords.is_empty() {
+        ExitCode::from(cli::EXIT_NO_CROSSWORD)
+    } else {
+        ExitCode::from(cli::EXIT_SOLUTION_FOUND)
+    }
+}
+
+fn print_crossword(crossword: &Crossword) {
+    crossword.print();
+    if crossword.words.iter().any(|word| word.clue.is_some()) {
+        println!("{}", clues::format_clue_lists(crossword));
+    }
+}
+
+fn save(arguments: &cli::Arguments, crosswords: &[Crossword]) {
+    if let Some(path) = &arguments.json_file {
+        if let Err(message) = cli::write_crosswords(path, crosswords) {
+            eprintln!("error: {}", message);
+        }
+    }
+    if let Some(path) = &arguments.svg_file {
+        let render = |crossword: &Crossword| Ok(svg::render(crossword, &arguments.svg_options));
+        if let Err(message) = cli::write_files(path, crosswords, render) {
+            eprintln!("error: {}", message);
+        }
+    }
+    if let Some(path) = &arguments.ipuz_file {
+        let render = |crossword: &Crossword| Ok(ipuz::to_ipuz(crossword));
+        if let Err(message) = cli::write_files(path, crosswords, render) {
+            eprintln!("error: {}", message);
+        }
+    }
+    if let Some(path) = &arguments.puz_file {
+        let render = |crossword: &Crossword| {
+            puz::to_puz(crossword, &PuzInfo::default()).map_err(|error| error.to_string())
+        };
+        if let Err(message) = cli::write_files(path, crosswords, render) {
+            eprintln!("error: {}", message);
+        }
+    }
+}
+
+fn bad_input(message: &str) -> ExitCode {
+    eprintln!("error: {}", message);
+    eprintln!();
+    eprintln!("{}", cli::USAGE);
+    ExitCode::from(cli::EXIT_BAD_INPUT)
+}
@@ -0,0 +1,20 @@
+use std::fmt::Debug;
+
+use crate::crossword::Crossword;
+use crate::solver::Solution;
+
+/// Hears about a search as it runs. Every method does nothing unless overridden, and calls are
+/// never made by two threads at once.
+pub trait Observer: Debug + Send + Sync {
+    /// A finished crossword was kept as one of the best so far.
+    fn found(&self, _crossword: &Crossword) {}
+
+    /// A finished crossword beat every crossword found before it. Called before [`found`] for the
+    /// same crossword.
+    ///
+    /// [`found`]: Observer::found
+    fn improved(&self, _crossword: &Crossword) {}
+
+    /// The search is over, and these are the crosswords it settled on.
+    fn finished(&self, _solution: &Solution) {}
+}
@@ -1,8 +1,20 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Instant;
 
 use crate::crossword::{CrossData, Crossword, Direction};
+use crate::grid::Grid;
 use crate::letters::WordAndLetter;
-use crate::output;
+use crate::pareto::{self, Objectives};
+use crate::progress::Counters;
+use crate::scorer::Scorer;
+use crate::solver::{Heuristic, Selection, SolverOptions};
+use crate::transpositions::Transpositions;
+
+/// How many nodes to visit between checks of whether progress is due.
+const REPORT_EVERY: u64 = 256;
 
 #[derive(Debug, PartialEq)]
 enum Comparison {
@@ -13,31 +25,416 @@ enum Comparison {
     SeedDuplicate,
 }
 
+pub struct Search<'a> {
+    pub letter_map: &'a HashMap<char, Vec<WordAndLetter<'a>>>,
+    pub options: &'a SolverOptions,
+    /// For each word, how many letters of the other words its letters could cross.
+    connectivity: Vec<usize>,
+    /// Whether candidates leave out letters which can no longer be crossed. Those insertions would
+    /// only be rejected, so this searches the same nodes with fewer rejected insertions.
+    skip_uncrossable: bool,
+    best_score: AtomicU64,
+    output_lock: Mutex<()>,
+    deadline: Option<Instant>,
+    stopped: AtomicBool,
+    pub(crate) counters: Counters,
+}
+
+impl<'a> Search<'a> {
+    pub fn new(
+        letter_map: &'a HashMap<char, Vec<WordAndLetter<'a>>>,
+        options: &'a SolverOptions,
+    ) -> Search<'a> {
+        Search {
+            letter_map,
+            options,
+            connectivity: get_connectivity(letter_map),
+            skip_uncrossable: true,
+            best_score: AtomicU64::new(u64::MAX),
+            output_lock: Mutex::new(()),
+            deadline: options
+                .time_limit
+                .map(|time_limit| Instant::now() + time_limit),
+            stopped: AtomicBool::new(false),
+            counters: Counters::new(),
+        }
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.stopped.load(Ordering::Relaxed)
+    }
+
+    fn check_stop(&self) -> bool {
+        if self.is_stopped() {
+            return true;
+        }
+
+        let cancelled = self
+            .options
+            .cancel
+            .as_ref()
+            .is_some_and(|cancel| cancel.is_cancelled());
+        let timed_out = self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline);
+
+        if cancelled || timed_out {
+            self.stopped.store(true, Ordering::Relaxed);
+        }
+
+        cancelled || timed_out
+    }
+
+    fn compare(&self, crossword: &Crossword, best_crosswords: &mut Vec<Crossword>) -> Comparison {
+        if let Selection::ParetoFront { crossings } = self.options.selection {
+            let bounds = Objectives::bounds(crossword, crossings);
+
+            return if bounds
+                .iter()
+                .all(|bound| pareto::is_dominated(bound, best_crosswords, crossings))
+            {
+                Comparison::Worse
+            } else if is_duplicate(crossword, best_crosswords) {
+                Comparison::SeedDuplicate
+            } else {
+                Comparison::AsGood
+            };
+        }
+
+        let scorer = self.options.scorer.as_ref();
+        let best_score = self.best_score.load(Ordering::Relaxed);
+
+        if !best_crosswords.is_empty() && score_key(scorer.score(&best_crosswords[0])) > best_score
+        {
+            best_crosswords.clear();
+        }
+        if best_crosswords.is_empty() {
+            // Some scorers have no bound, so a finished crossword is held to its score.
+            let bound = score_key(scorer.bound(crossword));
+            let score = crossword
+                .all_words_crossed()
+                .then(|| score_key(scorer.score(crossword)));
+            if bound > best_score || score.is_some_and(|score| score > best_score) {
+                return Comparison::Worse;
+            }
+        }
+
+        compare_crosswords(crossword, best_crosswords, scorer)
+    }
+}
+
 pub fn options_compare<'a>(
-    letter_map: &HashMap<char, Vec<WordAndLetter>>,
+    search: &Search,
+    crossword: &mut Crossword<'a>,
+    grid: &mut Grid,
+    transpositions: &mut Transpositions,
+    best_crosswords: &mut Vec<Crossword<'a>>,
+) {
+    for (row, mid_point, direction, word_and_letter) in get_candidates(search, crossword, grid) {
+        if insert_word(
+            row,
+            mid_point,
+            direction,
+            word_and_letter,
+            search.options,
+            crossword,
+            grid,
+        ) {
+            if transpositions.visit(grid.key()) {
+                Counters::count(&search.counters.duplicates);
+            } else {
+                options_branch(search, crossword, grid, transpositions, best_crosswords);
+            }
+
+            remove_word(word_and_letter, crossword, grid);
+        } else {
+            Counters::count(&search.counters.rejected);
+        }
+    }
+}
+
+pub fn options_compare_threaded<'a>(
+    search: &Search,
+    crossword: &mut Crossword<'a>,
+    grid: &mut Grid,
+    threads: usize,
+) -> Vec<Crossword<'a>> {
+    let mut branches = Vec::new();
+    for (row, mid_point, direction, word_and_letter) in get_candidates(search, crossword, grid) {
+        if insert_word(
+            row,
+            mid_point,
+            direction,
+            word_and_letter,
+            search.options,
+            crossword,
+            grid,
+        ) {
+            branches.push(crossword.clone());
+            remove_word(word_and_letter, crossword, grid);
+        } else {
+            Counters::count(&search.counters.rejected);
+        }
+    }
+
+    let next_branch = AtomicUsize::new(0);
+    let all_crosswords = Mutex::new(Vec::new());
+
+    thread::scope(|scope| {
+        for _ in 0..threads.max(1) {
+            scope.spawn(|| {
+                let mut best_crosswords = vec![];
+                let mut transpositions =
+                    Transpositions::new(search.options.transposition_limit / threads.max(1));
+
+                while let Some(branch) = branches.get(next_branch.fetch_add(1, Ordering::Relaxed)) {
+                    options_branch(
+                        search,
+                        &mut branch.clone(),
+                        &mut Grid::new(branch),
+                        &mut transpositions,
+                        &mut best_crosswords,
+                    );
+                }
+
+                all_crosswords.lock().unwrap().append(&mut best_crosswords);
+            });
+        }
+    });
+
+    merge_crosswords(all_crosswords.into_inner().unwrap(), search.options)
+}
+
+pub fn merge_crosswords<'a>(
+    mut crosswords: Vec<Crossword<'a>>,
+    options: &SolverOptions,
+) -> Vec<Crossword<'a>> {
+    let with_crossings = match options.selection {
+        Selection::Best => {
+            let score = |crossword: &Crossword| score_key(options.scorer.score(crossword));
+            if let Some(best_score) = crosswords.iter().map(score).min() {
+                crosswords.retain(|crossword| score(crossword) == best_score);
+            }
+            false
+        }
+        Selection::ParetoFront { crossings } => {
+            crosswords = pareto::front(crosswords, crossings);
+            crossings
+        }
+    };
+
+    crosswords.sort_by_key(|crossword| {
+        (
+            Objectives::new(crossword, with_crossings),
+            layout_key(crossword),
+            order_key(crossword),
+        )
+    });
+    let mut forms = HashSet::new();
+    crosswords.retain(|crossword| forms.insert(crossword.get_canonical_form()));
+
+    crosswords
+}
+
+fn options_branch<'a>(
+    search: &Search,
     crossword: &mut Crossword<'a>,
+    grid: &mut Grid,
+    transpositions: &mut Transpositions,
     best_crosswords: &mut Vec<Crossword<'a>>,
 ) {
+    if search.check_stop() {
+        return;
+    }
+
+    let nodes = Counters::count(&search.counters.nodes);
+    if let Some(progress) = &search.options.progress {
+        if nodes.is_multiple_of(REPORT_EVERY) {
+            search.counters.report(progress);
+        }
+    }
+
+    let crossword_status = search.compare(crossword, best_crosswords);
+
+    if crossword_status == Comparison::Worse {
+        Counters::count(&search.counters.pruned);
+    } else if crossword_status == Comparison::SeedDuplicate {
+        Counters::count(&search.counters.duplicates);
+    } else if crossword.all_words_crossed() {
+        if is_same_form(crossword, best_crosswords) {
+            Counters::count(&search.counters.duplicates);
+        } else if !search.options.block_overlaps || crossword.is_valid() {
+            let _output = search.output_lock.lock().unwrap();
+            let observer = search.options.observer.as_deref();
+            match search.options.selection {
+                Selection::Best => {
+                    let score = score_key(search.options.scorer.score(crossword));
+                    let previous_score = search.best_score.fetch_min(score, Ordering::Relaxed);
+                    if score < previous_score {
+                        search.counters.set_best_size(crossword.get_width_height());
+                        if previous_score != u64::MAX {
+                            if let Some(observer) = observer {
+                                observer.improved(crossword);
+                            }
+                        }
+                    }
+                    if add_crossword(crossword_status, crossword, best_crosswords) {
+                        if let Some(observer) = observer {
+                            observer.found(crossword);
+                        }
+                    }
+                }
+                Selection::ParetoFront { crossings } => {
+                    if add_pareto_crossword(crossword, best_crosswords, crossings) {
+                        if let Some(observer) = observer {
+                            observer.improved(crossword);
+                        }
+                    }
+                    if let Some(observer) = observer {
+                        observer.found(crossword);
+                    }
+                    search
+                        .counters
+                        .set_smaller_size(crossword.get_width_height());
+                }
+            }
+        }
+    } else {
+        options_compare(search, crossword, grid, transpositions, best_crosswords);
+    }
+}
+
+/// Every way to cross a placed letter with a word not yet placed, as the row, mid point and
+/// direction to insert the word at, in the order given by [`SolverOptions::heuristics`]. Letters
+/// which the grid shows cannot be crossed are left out.
+fn get_candidates<'a>(
+    search: &Search<'a>,
+    crossword: &Crossword,
+    grid: &Grid,
+) -> Vec<(i32, i32, Direction, &'a WordAndLetter<'a>)> {
+    let mut candidates = Vec::new();
     for (letter, row, mid_point, direction) in crossword.get_crossable_letters() {
-        if let Some(crossable_words) = letter_map.get(&letter) {
+        if search.skip_uncrossable
+            && !grid.is_crossable(get_position(row, mid_point, direction), direction)
+        {
+            continue;
+        }
+
+        if let Some(crossable_words) = search.letter_map.get(&letter) {
             for word_and_letter in crossable_words {
-                if insert_word(row, mid_point, direction, word_and_letter, crossword) {
-                    let crossword_status = compare_crosswords(crossword, best_crosswords);
+                if crossword.words[word_and_letter.word_index].cross.is_none() {
+                    candidates.push((row, mid_point, direction, word_and_letter));
+                }
+            }
+        }
+    }
 
-                    if crossword_status == Comparison::Worse
-                        || crossword_status == Comparison::SeedDuplicate
-                    {
-                    } else if crossword.all_words_crossed() {
-                        add_crossword(crossword_status, crossword, best_crosswords);
-                    } else {
-                        options_compare(letter_map, crossword, best_crosswords);
+    let heuristics = &search.options.heuristics;
+    if !heuristics.is_empty() {
+        // Twice the centre, so that it falls on a whole number.
+        let [x_low, y_low] = crossword.get_origin();
+        let (width, height) = crossword.get_width_height();
+        let centre = [2 * x_low + width as i32 - 1, 2 * y_low + height as i32 - 1];
+
+        candidates.sort_by_cached_key(|(row, mid_point, direction, word_and_letter)| {
+            heuristics
+                .iter()
+                .map(|heuristic| match heuristic {
+                    Heuristic::Longest => {
+                        -((word_and_letter.letter_index + word_and_letter.n_letters_after) as i64)
                     }
+                    Heuristic::Connectable => {
+                        -(search.connectivity[word_and_letter.word_index] as i64)
+                    }
+                    Heuristic::Central => {
+                        let [x, y] = get_position(*row, *mid_point, *direction);
+                        i64::from((2 * x - centre[0]).abs() + (2 * y - centre[1]).abs())
+                    }
+                })
+                .collect::<Vec<i64>>()
+        });
+    }
 
-                    remove_word(word_and_letter, crossword);
-                }
+    candidates
+}
+
+fn get_connectivity(letter_map: &HashMap<char, Vec<WordAndLetter>>) -> Vec<usize> {
+    let mut connectivity = Vec::new();
+    for words_with_letter in letter_map.values() {
+        for word_and_letter in words_with_letter {
+            let word_index = word_and_letter.word_index;
+            if connectivity.len() <= word_index {
+                connectivity.resize(word_index + 1, 0);
             }
+            connectivity[word_index] += words_with_letter
+                .iter()
+                .filter(|other| other.word_index != word_index)
+                .count();
         }
     }
+
+    connectivity
+}
+
+fn score_key(score: f64) -> u64 {
+    let bits = score.to_bits();
+    if bits >> 63 == 1 {
+        !bits
+    } else {
+        bits | 1 << 63
+    }
+}
+
+fn layout_key(crossword: &Crossword) -> Vec<Option<(i32, i32, usize)>> {
+    crossword
+        .words
+        .iter()
+        .map(|word| {
+            word.cross.as_ref().map(|cross_data| {
+                (
+                    cross_data.row,
+                    cross_data.start_point,
+                    cross_data.direction.index(),
+                )
+            })
+        })
+        .collect()
+}
+
+fn order_key(crossword: &Crossword) -> Vec<Option<usize>> {
+    crossword
+        .words
+        .iter()
+        .map(|word| word.cross.as_ref().map(|cross_data| cross_data.order))
+        .collect()
+}
+
+pub fn place_word(
+    word_index: usize,
+    row: i32,
+    start_point: i32,
+    direction: Direction,
+    options: &SolverOptions,
+    crossword: &mut Crossword,
+) -> bool {
+    let word = crossword.words[word_index].word;
+    let word_and_letter = WordAndLetter {
+        word_index,
+        word,
+        letter: word.chars().next().unwrap_or(' '),
+        letter_index: 0,
+        n_letters_after: word.chars().count().saturating_sub(1),
+    };
+
+    insert_word(
+        row,
+        start_point,
+        direction,
+        &word_and_letter,
+        options,
+        crossword,
+        &mut Grid::new(crossword),
+    )
 }
 
 fn insert_word(
@@ -45,7 +442,9 @@ fn insert_word(
     mid_p: i32,
     direction: Direction,
     word_l: &WordAndLetter,
+    options: &SolverOptions,
     crossword: &mut Crossword,
+    grid: &mut Grid,
 ) -> bool {
     let word_index = word_l.word_index;
 
@@ -53,8 +452,28 @@ fn insert_word(
         return false;
     }
 
-    let start_point = mid_p - word_l.letter_index as i32;
-    if !check_insertable(row, start_point, direction, word_l, crossword) {
+    let Some(start_point) = i32::try_from(word_l.letter_index)
+        .ok()
+        .and_then(|letter_index| mid_p.checked_sub(letter_index))
+    else {
+        return false;
+    };
+    let fits = CrossData {
+        row,
+        start_point,
+        direction,
+        order: 0,
+    }
+    .fits(word_l.word);
+    if !fits {
+        return false;
+    }
+
+    if options.block_overlaps {
+        if !check_overlappable(row, start_point, direction, word_l, grid) {
+            return false;
+        }
+    } else if !check_insertable(row, start_point, direction, word_l, grid) {
         return false;
     }
 
@@ -62,149 +481,155 @@ fn insert_word(
         row,
         start_point,
         direction,
-        order: crossword.get_next_order(),
+        order: grid.next_order(),
     };
     crossword.words[word_index].cross = Some(cross_data);
 
+    if let Some(max_size) = &options.max_size {
+        let (width, height) = crossword.get_width_height();
+        if !max_size.fits(width, height) {
+            crossword.words[word_index].cross = None;
+            return false;
+        }
+    }
+
+    if let Some(cross_data) = &crossword.words[word_index].cross {
+        grid.insert(word_index, word_l.word, cross_data);
+    }
+
     true
 }
 
+/// A word may cross others only where the letters match, and must not run on from another word,
+/// so the cells before and after it are empty. Nor may it run alongside another word: a cell
+/// which no word crosses must have empty neighbours, and a word in a neighbouring row may only
+/// touch it at a single cell, where a crossing word joins the two.
 fn check_insertable(
     new_row: i32,
     new_start: i32,
     direction: Direction,
     word_and_letter: &WordAndLetter,
-    crossword: &Crossword,
+    grid: &Grid,
 ) -> bool {
-    let new_end = get_end_point(new_start, word_and_letter.word);
-
-    for word in &crossword.words {
-        if let Some(cross_data) = &word.cross {
-            let old_start = cross_data.start_point;
-            let old_end = get_end_point(old_start, word.word);
-            let old_row = cross_data.row;
-
-            if (cross_data.direction == direction
-                && !check_same_direction(
-                    new_start, new_end, new_row, old_start, old_end, old_row, direction, crossword,
-                ))
-                || (cross_data.direction != direction
-                    && !check_different_direction(
-                        new_start,
-                        new_end,
-                        new_row,
-                        word_and_letter.word,
-                        old_start,
-                        old_end,
-                        old_row,
-                        word.word,
-                    ))
-            {
-                return false;
+    let index = direction.index();
+    let other_direction = direction.change();
+    let mut position = get_position(new_row, new_start - 1, direction);
+    if grid.letter(position).is_some() {
+        return false;
+    }
+
+    let mut alongside = [false; 2];
+    for letter in word_and_letter.word.chars() {
+        position[index] += 1;
+        let neighbours = get_neighbours(position, other_direction);
+        match grid.letter(position) {
+            Some(old_letter) => {
+                if old_letter != letter || grid.has_word(position, direction) {
+                    return false;
+                }
+                for (neighbour, was_alongside) in neighbours.into_iter().zip(&mut alongside) {
+                    let is_alongside = grid.has_word(neighbour, direction);
+                    if is_alongside
+                        && (*was_alongside || !grid.has_word(neighbour, other_direction))
+                    {
+                        return false;
+                    }
+                    *was_alongside = is_alongside;
+                }
+            }
+            None => {
+                if neighbours
+                    .into_iter()
+                    .any(|neighbour| grid.letter(neighbour).is_some())
+                {
+                    return false;
+                }
+                alongside = [false; 2];
             }
         }
     }
 
-    true
-}
-
-fn get_end_point(start: i32, word: &str) -> i32 {
-    start + word.len() as i32 - 1
+    position[index] += 1;
+    grid.letter(position).is_none()
 }
 
-fn check_same_direction(
-    start0: i32,
-    end0: i32,
-    row0: i32,
-    start1: i32,
-    end1: i32,
-    row1: i32,
+/// As [`check_insertable`], but words in the same direction may lie side by side, as long as no
+/// word runs on from another or ends against the side of one.
+fn check_overlappable(
+    new_row: i32,
+    new_start: i32,
     direction: Direction,
-    crossword: &Crossword,
+    word_and_letter: &WordAndLetter,
+    grid: &Grid,
 ) -> bool {
-    if row0 < row1 - 1 || row1 < row0 - 1 {
-        true
-    } else if row0 == row1 - 1 || row1 == row0 - 1 {
-        if end0 < start1 || end1 < start0 {
-            true
-        } else if end0 == start1 {
-            check_connecting_word(row0, row1, end0, direction.change(), crossword)
-        } else if end1 == start0 {
-            check_connecting_word(row0, row1, end1, direction.change(), crossword)
-        } else {
-            false
-        }
-    } else {
-        end0 < start1 - 1 || end1 < start0 - 1
+    let index = direction.index();
+    let other_direction = direction.change();
+    let mut position = get_position(new_row, new_start - 1, direction);
+    if grid.letter(position).is_some() {
+        return false;
     }
-}
 
-fn check_connecting_word(
-    point0: i32,
-    point1: i32,
-    row: i32,
-    direction: Direction,
-    crossword: &Crossword,
-) -> bool {
-    for word in &crossword.words {
-        if let Some(cross_data) = &word.cross {
-            if cross_data.direction == direction
-                && cross_data.row == row
-                && cross_data.start_point <= point0.min(point1)
-                && cross_data.start_point + word.word.len() as i32 > point0.max(point1)
-            {
-                return true;
+    for letter in word_and_letter.word.chars() {
+        position[index] += 1;
+        if grid.has_word(position, direction) {
+            return false;
+        }
+        if grid.has_word(position, other_direction) {
+            if grid.letter(position) != Some(letter) {
+                return false;
+            }
+        } else {
+            let [before, after] = get_neighbours(position, other_direction);
+            if grid.has_word(before, other_direction) || grid.has_word(after, other_direction) {
+                return false;
             }
         }
     }
 
-    false
+    position[index] += 1;
+    grid.letter(position).is_none()
 }
 
-fn check_different_direction(
-    start0: i32,
-    end0: i32,
-    row1: i32,
-    word0: &str,
-    start1: i32,
-    end1: i32,
-    row0: i32,
-    word1: &str,
-) -> bool {
-    if end0 < row0 - 1 || start0 > row0 + 1 || end1 < row1 - 1 || start1 > row1 + 1 {
-        true
-    } else if ((end0 == row0 - 1 || start0 == row0 + 1) && start1 <= row1 && row1 <= end1)
-        || ((end1 == row1 - 1 || start1 == row1 + 1) && start0 <= row0 && row0 <= end0)
-    {
-        false
-    } else {
-        let letter0 = get_nth_letter(word0, row0 - start0);
-        let letter1 = get_nth_letter(word1, row1 - start1);
-        letter0 == letter1
+/// The `[x, y]` coordinates of a point along a row or column.
+fn get_position(row: i32, point: i32, direction: Direction) -> [i32; 2] {
+    match direction {
+        Direction::Across => [point, row],
+        Direction::Down => [row, point],
     }
 }
 
-fn get_nth_letter(word: &str, index: i32) -> char {
-    for (i, l) in word.chars().enumerate() {
-        if i as i32 == index {
-            return l;
-        }
-    }
-    ' '
+/// The cells either side of `position` in `direction`.
+fn get_neighbours(position: [i32; 2], direction: Direction) -> [[i32; 2]; 2] {
+    let index = direction.index();
+    let mut before = position;
+    let mut after = position;
+    before[index] -= 1;
+    after[index] += 1;
+
+    [before, after]
 }
 
-fn compare_crosswords(crossword: &Crossword, best_crosswords: &[Crossword]) -> Comparison {
+fn compare_crosswords(
+    crossword: &Crossword,
+    best_crosswords: &[Crossword],
+    scorer: &dyn Scorer,
+) -> Comparison {
     if best_crosswords.is_empty() {
         Comparison::First
     } else {
-        let (current_min, current_max) = best_crosswords[0].get_min_max();
-        let (new_min, new_max) = crossword.get_min_max();
+        let current_score = scorer.score(&best_crosswords[0]);
+        let new_bound = scorer.bound(crossword);
+        let new_score = if crossword.all_words_crossed() {
+            scorer.score(crossword)
+        } else {
+            new_bound
+        };
 
-        if (new_max > current_max) || (new_max == current_max && new_min > current_min) {
+        if new_bound > current_score || new_score > current_score {
             Comparison::Worse
         } else if is_duplicate(crossword, best_crosswords) {
             Comparison::SeedDuplicate
-        } else if (new_max == current_max) && (new_min == current_min) {
+        } else if new_score == current_score {
             Comparison::AsGood
         } else {
             Comparison::Better
@@ -256,13 +681,21 @@ fn is_duplicate(crossword: &Crossword, best_crosswords: &[Crossword]) -> bool {
     duplicate
 }
 
+/// Whether a crossword which looks the same has already been kept.
+fn is_same_form(crossword: &Crossword, best_crosswords: &[Crossword]) -> bool {
+    let form = crossword.get_canonical_form();
+    best_crosswords
+        .iter()
+        .any(|good_crossword| good_crossword.get_canonical_form() == form)
+}
+
+/// Keeps the crossword if it is as good as the best so far, and returns whether it was kept.
 fn add_crossword<'a>(
     comparison: Comparison,
     crossword: &Crossword<'a>,
     best_crosswords: &mut Vec<Crossword<'a>>,
-) {
+) -> bool {
     if comparison == Comparison::Better {
-        output::clear_message("Selection improved!");
         while !best_crosswords.is_empty() {
             best_crosswords.pop();
         }
@@ -270,18 +703,38 @@ fn add_crossword<'a>(
 
     match comparison {
         Comparison::First | Comparison::Better | Comparison::AsGood => {
-            crossword.print();
             best_crosswords.push(crossword.clone());
+            true
         }
-        Comparison::Worse | Comparison::SeedDuplicate => {}
+        Comparison::Worse | Comparison::SeedDuplicate => false,
     }
 }
 
-fn remove_word(word_and_letter: &WordAndLetter, crossword: &mut Crossword) {
+/// Keeps the crossword, and returns whether it beat any of the crosswords kept before.
+fn add_pareto_crossword<'a>(
+    crossword: &Crossword<'a>,
+    best_crosswords: &mut Vec<Crossword<'a>>,
+    with_crossings: bool,
+) -> bool {
+    let objectives = Objectives::new(crossword, with_crossings);
+    let n_crosswords = best_crosswords.len();
+    best_crosswords.retain(|good_crossword| {
+        !objectives.dominates(&Objectives::new(good_crossword, with_crossings))
+    });
+    let improved = best_crosswords.len() < n_crosswords;
+
+    best_crosswords.push(crossword.clone());
+    improved
+}
+
+fn remove_word(word_and_letter: &WordAndLetter, crossword: &mut Crossword, grid: &mut Grid) {
     let word_index = word_and_letter.word_index;
-    crossword.words[word_index].cross = None;
+    if let Some(cross_data) = crossword.words[word_index].cross.take() {
+        grid.remove(word_index, word_and_letter.word, &cross_data);
+    }
 }
 
 #[cfg(test)]
 #[path = "./tests_options.rs"]
+#[allow(clippy::partialeq_to_none)]
 mod tests_options;
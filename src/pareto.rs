@@ -0,0 +1,84 @@
+use crate::crossword::Crossword;
+
+/// Sizes compared when keeping the Pareto front of crosswords. Crossings are only counted when
+/// asked for, and are otherwise left at 0.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Objectives {
+    pub width: usize,
+    pub height: usize,
+    pub crossings: usize,
+}
+
+impl Objectives {
+    pub fn new(crossword: &Crossword, with_crossings: bool) -> Objectives {
+        let (width, height) = crossword.get_width_height();
+        let crossings = if with_crossings {
+            crossword.count_crossings()
+        } else {
+            0
+        };
+
+        Objectives {
+            width,
+            height,
+            crossings,
+        }
+    }
+
+    /// Best objectives that any completion of a partial crossword could reach, one for each of
+    /// its [size bounds]. A completion is at least as large as one of them.
+    ///
+    /// [size bounds]: Crossword::get_size_bounds
+    pub fn bounds(crossword: &Crossword, with_crossings: bool) -> Vec<Objectives> {
+        let crossings = if with_crossings {
+            crossword.count_crossings() + crossword.count_uncrossed_letters()
+        } else {
+            0
+        };
+
+        crossword
+            .get_size_bounds()
+            .into_iter()
+            .map(|(width, height)| Objectives {
+                width,
+                height,
+                crossings,
+            })
+            .collect()
+    }
+
+    pub fn dominates(&self, other: &Objectives) -> bool {
+        self.width <= other.width
+            && self.height <= other.height
+            && self.crossings >= other.crossings
+            && self != other
+    }
+}
+
+pub fn is_dominated(
+    objectives: &Objectives,
+    crosswords: &[Crossword],
+    with_crossings: bool,
+) -> bool {
+    crosswords
+        .iter()
+        .any(|crossword| Objectives::new(crossword, with_crossings).dominates(objectives))
+}
+
+pub fn front(crosswords: Vec<Crossword>, with_crossings: bool) -> Vec<Crossword> {
+    let objectives: Vec<Objectives> = crosswords
+        .iter()
+        .map(|crossword| Objectives::new(crossword, with_crossings))
+        .collect();
+
+    crosswords
+        .into_iter()
+        .zip(&objectives)
+        .filter(|(_, current)| !objectives.iter().any(|other| other.dominates(current)))
+        .map(|(crossword, _)| crossword)
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "./tests_pareto.rs"]
+mod tests_pareto;
@@ -0,0 +1,142 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How far a search has got.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Statistics {
+    /// Partial crosswords visited.
+    pub nodes: u64,
+    /// Insertions which failed the placement checks, or made the crossword too large.
+    pub rejected: u64,
+    /// Branches cut because they could not beat the best crosswords found so far.
+    pub pruned: u64,
+    /// Branches cut because they repeat a crossword which was already kept.
+    pub duplicates: u64,
+    /// Width and height of the best crossword found so far. For a Pareto front, the one with the
+    /// smallest area.
+    pub best_size: Option<(usize, usize)>,
+    pub elapsed: Duration,
+}
+
+/// Reports [`Statistics`] while searching, at most once per `interval`, and once more when the
+/// search finishes.
+#[derive(Clone)]
+pub struct Progress {
+    pub interval: Duration,
+    pub callback: Arc<dyn Fn(&Statistics) + Send + Sync>,
+}
+
+/// Counts updated by every thread of a search.
+#[derive(Debug)]
+pub(crate) struct Counters {
+    pub(crate) nodes: AtomicU64,
+    pub(crate) rejected: AtomicU64,
+    pub(crate) pruned: AtomicU64,
+    pub(crate) duplicates: AtomicU64,
+    best_size: Mutex<Option<(usize, usize)>>,
+    start: Instant,
+    last_report: Mutex<Instant>,
+}
+
+impl fmt::Display for Statistics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} nodes, {} rejected, {} pruned, {} duplicates, ",
+            self.nodes, self.rejected, self.pruned, self.duplicates
+        )?;
+        match self.best_size {
+            Some((width, height)) => write!(f, "best {} x {}, ", width, height)?,
+            None => write!(f, "nothing found yet, ")?,
+        }
+        write!(f, "{:.1}s", self.elapsed.as_secs_f64())
+    }
+}
+
+impl Progress {
+    pub fn new(
+        interval: Duration,
+        callback: impl Fn(&Statistics) + Send + Sync + 'static,
+    ) -> Progress {
+        Progress {
+            interval,
+            callback: Arc::new(callback),
+        }
+    }
+
+    /// Prints each report as a line on stderr.
+    pub fn stderr(interval: Duration) -> Progress {
+        Progress::new(interval, |statistics| eprintln!("{}", statistics))
+    }
+}
+
+impl fmt::Debug for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Progress")
+            .field("interval", &self.interval)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Counters {
+    pub(crate) fn new() -> Counters {
+        let start = Instant::now();
+
+        Counters {
+            nodes: AtomicU64::new(0),
+            rejected: AtomicU64::new(0),
+            pruned: AtomicU64::new(0),
+            duplicates: AtomicU64::new(0),
+            best_size: Mutex::new(None),
+            start,
+            last_report: Mutex::new(start),
+        }
+    }
+
+    pub(crate) fn count(counter: &AtomicU64) -> u64 {
+        counter.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    pub(crate) fn set_best_size(&self, size: (usize, usize)) {
+        *self.best_size.lock().unwrap() = Some(size);
+    }
+
+    /// Keeps the size with the smaller area.
+    pub(crate) fn set_smaller_size(&self, (width, height): (usize, usize)) {
+        let mut best_size = self.best_size.lock().unwrap();
+        if best_size
+            .is_none_or(|(best_width, best_height)| width * height < best_width * best_height)
+        {
+            *best_size = Some((width, height));
+        }
+    }
+
+    pub(crate) fn statistics(&self) -> Statistics {
+        Statistics {
+            nodes: self.nodes.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+            pruned: self.pruned.load(Ordering::Relaxed),
+            duplicates: self.duplicates.load(Ordering::Relaxed),
+            best_size: *self.best_size.lock().unwrap(),
+            elapsed: self.start.elapsed(),
+        }
+    }
+
+    /// Reports if the interval has passed since the last report. Threads which find another
+    /// thread already reporting carry on searching.
+    pub(crate) fn report(&self, progress: &Progress) {
+        let Ok(mut last_report) = self.last_report.try_lock() else {
+            return;
+        };
+        if last_report.elapsed() >= progress.interval {
+            *last_report = Instant::now();
+            (progress.callback)(&self.statistics());
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "./tests_progress.rs"]
+mod tests_progress;
@@ -0,0 +1,207 @@
+use std::fmt;
+
+use crate::clues;
+use crate::crossword::Crossword;
+use crate::letters;
+
+const MAGIC: &[u8; 12] = b"ACROSS&DOWN\0";
+const VERSION: &[u8; 4] = b"1.3\0";
+const MASK: &[u8; 8] = b"ICHEATED";
+const HEADER_SIZE: usize = 0x34;
+const BLACK: u8 = b'.';
+const EMPTY: u8 = b'-';
+
+/// The text written alongside the grid.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PuzInfo {
+    pub title: String,
+    pub author: String,
+    pub copyright: String,
+    pub notes: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PuzError {
+    /// The grid is wider or taller than 255 cells.
+    TooLarge(usize, usize),
+    /// A letter or clue uses a character outside ISO-8859-1.
+    Unencodable(char),
+}
+
+impl fmt::Display for PuzError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PuzError::TooLarge(width, height) => {
+                write!(f, "a {}x{} grid is too large for .puz", width, height)
+            }
+            PuzError::Unencodable(character) => {
+                write!(f, "'{}' cannot be written to .puz", character)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PuzError {}
+
+/// Writes a crossword as an Across Lite `.puz` file. The grid is the bounding box of the
+/// crossword, with its empty cells as black squares. Clues are listed by number, Across before
+/// Down, with the enumeration added when one was given.
+pub fn to_puz(crossword: &Crossword, info: &PuzInfo) -> Result<Vec<u8>, PuzError> {
+    let letters = crossword.get_letters();
+    let (x_low, y_low, width, height) = if letters.is_empty() {
+        (0, 0, 0, 0)
+    } else {
+        let [x_low, y_low] = crossword.get_origin();
+        let (width, height) = crossword.get_width_height();
+        (x_low, y_low, width, height)
+    };
+    let (Ok(width_byte), Ok(height_byte)) = (u8::try_from(width), u8::try_from(height)) else {
+        return Err(PuzError::TooLarge(width, height));
+    };
+
+    let mut solution = Vec::with_capacity(width * height);
+    for y in y_low..y_low + height as i32 {
+        for x in x_low..x_low + width as i32 {
+            match letters.get(&[x, y]) {
+                Some(cell) => {
+                    // A digraph cell is written as its first letter, as for a rebus square
+                    // without the rebus sections.
+                    let letter = letters::get_cell_text(*cell)
+                        .chars()
+                        .next()
+                        .unwrap_or(*cell);
+                    // Keep letters such as 'ß' whose capital is more than one letter.
+                    let mut upper = letter.to_uppercase();
+                    let letter = match (upper.next(), upper.next()) {
+                        (Some(upper), None) => upper,
+                        _ => letter,
+                    };
+                    solution.extend(encode(&letter.to_string())?);
+                }
+                None => solution.push(BLACK),
+            }
+        }
+    }
+    let grid: Vec<u8> = solution
+        .iter()
+        .map(|cell| if *cell == BLACK { BLACK } else { EMPTY })
+        .collect();
+
+    let numbering = clues::numbering(crossword);
+    let mut entries: Vec<&clues::Entry> = numbering.across.iter().chain(&numbering.down).collect();
+    entries.sort_by_key(|entry| entry.number);
+    let mut clue_strings = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let text = match crossword.words[entry.word_index].clue {
+            Some(clue) => match &clue.enumeration {
+                Some(enumeration) => format!("{} {}", clue.text, enumeration),
+                None => clue.text.clone(),
+            },
+            None => String::new(),
+        };
+        clue_strings.push(encode(&text)?);
+    }
+
+    let title = encode(&info.title)?;
+    let author = encode(&info.author)?;
+    let copyright = encode(&info.copyright)?;
+    let notes = encode(&info.notes)?;
+
+    let mut header = vec![0; HEADER_SIZE];
+    header[0x02..0x0E].copy_from_slice(MAGIC);
+    header[0x18..0x1C].copy_from_slice(VERSION);
+    header[0x2C] = width_byte;
+    header[0x2D] = height_byte;
+    header[0x2E..0x30].copy_from_slice(&(clue_strings.len() as u16).to_le_bytes());
+    header[0x30..0x32].copy_from_slice(&1_u16.to_le_bytes());
+
+    let cib_checksum = checksum(&header[0x2C..0x34], 0);
+    let solution_checksum = checksum(&solution, 0);
+    let grid_checksum = checksum(&grid, 0);
+    let strings_checksum = text_checksum(&title, &author, &copyright, &clue_strings, &notes, 0);
+    let mut file_checksum = checksum(&solution, cib_checksum);
+    file_checksum = checksum(&grid, file_checksum);
+    file_checksum = text_checksum(
+        &title,
+        &author,
+        &copyright,
+        &clue_strings,
+        &notes,
+        file_checksum,
+    );
+
+    header[0x00..0x02].copy_from_slice(&file_checksum.to_le_bytes());
+    header[0x0E..0x10].copy_from_slice(&cib_checksum.to_le_bytes());
+    let checksums = [
+        cib_checksum,
+        solution_checksum,
+        grid_checksum,
+        strings_checksum,
+    ];
+    for (index, checksum) in checksums.iter().enumerate() {
+        header[0x10 + index] = MASK[index] ^ (checksum & 0xFF) as u8;
+        header[0x14 + index] = MASK[index + 4] ^ (checksum >> 8) as u8;
+    }
+
+    let mut puz = header;
+    puz.extend(&solution);
+    puz.extend(&grid);
+    for string in [&title, &author, &copyright]
+        .into_iter()
+        .chain(&clue_strings)
+        .chain([&notes])
+    {
+        puz.extend(string);
+        puz.push(0);
+    }
+
+    Ok(puz)
+}
+
+/// The checksum used throughout `.puz` files: rotate right, then add the next byte.
+pub fn checksum(data: &[u8], start: u16) -> u16 {
+    let mut checksum = start;
+    for byte in data {
+        checksum = checksum.rotate_right(1).wrapping_add(u16::from(*byte));
+    }
+
+    checksum
+}
+
+/// Sums the strings as the format expects: the title, author, copyright and notes with their
+/// terminating nul and only when not empty, and the clues without their nul.
+fn text_checksum(
+    title: &[u8],
+    author: &[u8],
+    copyright: &[u8],
+    clue_strings: &[Vec<u8>],
+    notes: &[u8],
+    start: u16,
+) -> u16 {
+    let with_nul = |sum: u16, string: &[u8]| {
+        if string.is_empty() {
+            sum
+        } else {
+            checksum(&[0], checksum(string, sum))
+        }
+    };
+
+    let mut sum = with_nul(start, title);
+    sum = with_nul(sum, author);
+    sum = with_nul(sum, copyright);
+    for clue in clue_strings {
+        sum = checksum(clue, sum);
+    }
+    with_nul(sum, notes)
+}
+
+/// Encodes text as ISO-8859-1, the character set of `.puz` files.
+fn encode(text: &str) -> Result<Vec<u8>, PuzError> {
+    text.chars()
+        .map(|character| u8::try_from(character).map_err(|_| PuzError::Unencodable(character)))
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "./tests_puz.rs"]
+mod tests_puz;
@@ -0,0 +1,158 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use crate::crossword::Crossword;
+
+/// Decides which crosswords are best. Lower scores are better.
+pub trait Scorer: Debug + Send + Sync {
+    /// Score of a crossword with every word placed.
+    fn score(&self, crossword: &Crossword) -> f64;
+
+    /// Lowest score that any completion of this partial crossword could reach. The search drops
+    /// partial crosswords whose bound is worse than the best score found so far.
+    fn bound(&self, crossword: &Crossword) -> f64 {
+        self.score(crossword)
+    }
+}
+
+/// Smaller longest side, then smaller shortest side.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BoundingBox;
+
+/// Smaller width x height.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Area;
+
+/// Smaller width + height.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Perimeter;
+
+/// Ratio of the longest side to the shortest side closer to `target`.
+#[derive(Clone, Copy, Debug)]
+pub struct AspectRatio {
+    pub target: f64,
+}
+
+/// More letters shared between an Across word and a Down word.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Crossings;
+
+/// Larger fraction of the bounding box filled with letters.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Density;
+
+/// Sum of other scores, each multiplied by a weight. Weights must not be negative.
+#[derive(Clone, Debug, Default)]
+pub struct Weighted {
+    pub scorers: Vec<(f64, Arc<dyn Scorer>)>,
+}
+
+const BOUNDING_BOX_SCALE: f64 = 1_000_000.0;
+
+impl Scorer for BoundingBox {
+    fn score(&self, crossword: &Crossword) -> f64 {
+        let (width, height) = crossword.get_width_height();
+        bounding_box(width, height)
+    }
+
+    fn bound(&self, crossword: &Crossword) -> f64 {
+        size_bound(crossword, bounding_box)
+    }
+}
+
+impl Scorer for Area {
+    fn score(&self, crossword: &Crossword) -> f64 {
+        let (width, height) = crossword.get_width_height();
+        area(width, height)
+    }
+
+    fn bound(&self, crossword: &Crossword) -> f64 {
+        size_bound(crossword, area)
+    }
+}
+
+impl Scorer for Perimeter {
+    fn score(&self, crossword: &Crossword) -> f64 {
+        let (width, height) = crossword.get_width_height();
+        perimeter(width, height)
+    }
+
+    fn bound(&self, crossword: &Crossword) -> f64 {
+        size_bound(crossword, perimeter)
+    }
+}
+
+impl Scorer for AspectRatio {
+    fn score(&self, crossword: &Crossword) -> f64 {
+        let (min, max) = crossword.get_min_max();
+        let target = self.target.max(1.0 / self.target);
+        ((max as f64 / min as f64).ln() - target.ln()).abs()
+    }
+
+    fn bound(&self, _crossword: &Crossword) -> f64 {
+        0.0
+    }
+}
+
+impl Scorer for Crossings {
+    fn score(&self, crossword: &Crossword) -> f64 {
+        -(crossword.count_crossings() as f64)
+    }
+
+    fn bound(&self, crossword: &Crossword) -> f64 {
+        -((crossword.count_crossings() + crossword.count_uncrossed_letters()) as f64)
+    }
+}
+
+impl Scorer for Density {
+    fn score(&self, crossword: &Crossword) -> f64 {
+        let (width, height) = crossword.get_width_height();
+        -(crossword.count_cells() as f64 / (width * height) as f64)
+    }
+
+    fn bound(&self, _crossword: &Crossword) -> f64 {
+        -1.0
+    }
+}
+
+impl Scorer for Weighted {
+    fn score(&self, crossword: &Crossword) -> f64 {
+        self.scorers
+            .iter()
+            .map(|(weight, scorer)| weight * scorer.score(crossword))
+            .sum()
+    }
+
+    fn bound(&self, crossword: &Crossword) -> f64 {
+        self.scorers
+            .iter()
+            .map(|(weight, scorer)| weight * scorer.bound(crossword))
+            .sum()
+    }
+}
+
+fn bounding_box(width: usize, height: usize) -> f64 {
+    width.max(height) as f64 * BOUNDING_BOX_SCALE + width.min(height) as f64
+}
+
+fn area(width: usize, height: usize) -> f64 {
+    (width * height) as f64
+}
+
+fn perimeter(width: usize, height: usize) -> f64 {
+    (2 * (width + height)) as f64
+}
+
+/// Lowest score of any size the crossword could finish at, for a score which grows with the width
+/// and height. See [`Crossword::get_size_bounds`].
+fn size_bound(crossword: &Crossword, score: fn(usize, usize) -> f64) -> f64 {
+    crossword
+        .get_size_bounds()
+        .into_iter()
+        .map(|(width, height)| score(width, height))
+        .fold(f64::INFINITY, f64::min)
+}
+
+#[cfg(test)]
+#[path = "./tests_scorer.rs"]
+mod tests_scorer;
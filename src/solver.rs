@@ -0,0 +1,398 @@
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::clues::Clue;
+use crate::crossword::{self, CrossData, Crossword, Direction};
+use crate::grid::Grid;
+use crate::letters::{self, Normalisation};
+use crate::observer::Observer;
+use crate::options::{
+    merge_crosswords, options_compare, options_compare_threaded, place_word, Search,
+};
+use crate::progress::{Progress, Statistics};
+use crate::scorer::{BoundingBox, Scorer};
+use crate::transpositions::Transpositions;
+
+/// Partial crosswords remembered by default, up to about 32 MiB.
+pub const DEFAULT_TRANSPOSITION_LIMIT: usize = 1 << 20;
+
+/// Options for a [`Solver`]. Construct with `SolverOptions::default()`, as fields may be added.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct SolverOptions {
+    /// Allow words to be placed alongside each other, so that overlaps of 2x2 and larger can be
+    /// found. Crosswords are only accepted once every pair of neighbouring letters is part of a word.
+    pub block_overlaps: bool,
+    /// Number of threads to search with. 0 uses every available core.
+    pub threads: usize,
+    /// Stop searching after this long and return the best crosswords found so far.
+    pub time_limit: Option<Duration>,
+    /// Stop searching once this token is cancelled, e.g. from another thread.
+    pub cancel: Option<CancelToken>,
+    /// Decides which crosswords are best. Defaults to [`BoundingBox`].
+    pub scorer: Arc<dyn Scorer>,
+    /// Whether to keep the best crosswords by score, or the Pareto front.
+    pub selection: Selection,
+    /// Largest grid allowed. Insertions which make the crossword larger are rejected.
+    pub max_size: Option<MaxSize>,
+    /// How words are turned into cells, e.g. whether "é" crosses "e".
+    pub normalisation: Normalisation,
+    /// Reports how the search is going while it runs.
+    pub progress: Option<Progress>,
+    /// Told about crosswords as they are found. Without one, the search prints nothing.
+    pub observer: Option<Arc<dyn Observer>>,
+    /// Most partial crosswords to remember as searched, so that one reached again with its words
+    /// placed in another order is skipped. The limit is split evenly between the threads, each of
+    /// which remembers only what it has searched. Each takes about 32 bytes. 0 remembers none.
+    pub transposition_limit: usize,
+    /// Which placements to try first, each heuristic breaking the ties of the one before. Empty
+    /// tries them in the order the words were given. The same crosswords are found whatever the
+    /// order, but finding good ones early lets more of the search be skipped.
+    pub heuristics: Vec<Heuristic>,
+}
+
+/// Largest grid a crossword may fill.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MaxSize {
+    pub width: usize,
+    pub height: usize,
+    /// Also allow crosswords which fit once turned sideways.
+    pub rotatable: bool,
+}
+
+/// Which crosswords the solver keeps.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Selection {
+    /// The crosswords with the best score from [`SolverOptions::scorer`].
+    #[default]
+    Best,
+    /// Every crossword which no other crossword beats on width and height, and also on number of
+    /// crossings if `crossings` is set. The scorer is not used.
+    ParetoFront { crossings: bool },
+}
+
+/// A rule for which placements the search tries first.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Heuristic {
+    /// Longer words first.
+    Longest,
+    /// Words whose letters appear more often in the other words first, as they leave more places
+    /// for the rest to cross.
+    Connectable,
+    /// Crossings nearer the middle of the crossword first, as they are less likely to make it
+    /// larger.
+    Central,
+}
+
+/// Shared flag used to stop a running search early.
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+/// The best crosswords found by [`Solver::solve`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Solution<'a> {
+    pub crosswords: Vec<Crossword<'a>>,
+    /// Whether every option was considered. False if the search was stopped early.
+    pub exhaustive: bool,
+    pub statistics: Statistics,
+}
+
+impl Default for SolverOptions {
+    fn default() -> SolverOptions {
+        SolverOptions {
+            block_overlaps: false,
+            threads: 1,
+            time_limit: None,
+            cancel: None,
+            scorer: Arc::new(BoundingBox),
+            selection: Selection::Best,
+            max_size: None,
+            normalisation: Normalisation::default(),
+            progress: None,
+            observer: None,
+            transposition_limit: DEFAULT_TRANSPOSITION_LIMIT,
+            heuristics: Vec::new(),
+        }
+    }
+}
+
+impl fmt::Display for SeedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SeedError::Unplaceable(word) => write!(f, "seed '{}' cannot be placed there", word),
+            SeedError::Disconnected => write!(f, "seeds do not form a single crossword"),
+        }
+    }
+}
+
+impl std::error::Error for SeedError {}
+
+impl MaxSize {
+    pub fn fits(&self, width: usize, height: usize) -> bool {
+        (width <= self.width && height <= self.height)
+            || (self.rotatable && width <= self.height && height <= self.width)
+    }
+}
+
+impl CancelToken {
+    pub fn new() -> CancelToken {
+        CancelToken::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// A word pinned in place before the search starts. As in [`CrossData`], `row` is the y
+/// coordinate of an Across word and the x coordinate of a Down word.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Seed {
+    pub word: String,
+    pub row: i32,
+    pub start_point: i32,
+    pub direction: Direction,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SeedError {
+    /// The seed clashes with an earlier seed, or does not fit within the maximum size.
+    Unplaceable(String),
+    /// The seeds do not all cross each other to form a single crossword.
+    Disconnected,
+}
+
+/// Compresses a selection of words into the best crosswords.
+pub struct Solver {
+    words: Vec<String>,
+    display: Vec<Option<String>>,
+    options: SolverOptions,
+    seeds: Vec<(usize, CrossData)>,
+    clues: Vec<Option<Clue>>,
+}
+
+impl Solver {
+    /// Takes the words to place. Each is normalised into its cells with
+    /// [`SolverOptions::normalisation`], keeping the word as given for display.
+    pub fn new<S: AsRef<str>>(words: &[S], options: SolverOptions) -> Solver {
+        let (words, display) = words
+            .iter()
+            .map(|word| normalise(word.as_ref(), &options.normalisation))
+            .unzip();
+
+        Solver {
+            words,
+            display,
+            options,
+            seeds: Vec::new(),
+            clues: Vec::new(),
+        }
+    }
+
+    /// Attaches clues to the words, in the same order as the words given to [`Solver::new`]. The
+    /// clues are carried through to every [`WordCross`](crate::WordCross) of the solutions.
+    pub fn with_clues(mut self, clues: Vec<Option<Clue>>) -> Solver {
+        self.clues = clues;
+        self
+    }
+
+    /// Pins words in place, instead of placing the first word Across at (0, 0). Seed words which
+    /// are not in the word list are added to it.
+    pub fn with_seeds(mut self, seeds: &[Seed]) -> Result<Solver, SeedError> {
+        let mut word_indices: Vec<usize> = Vec::with_capacity(seeds.len());
+
+        for seed in seeds {
+            let (word, display) = normalise(&seed.word, &self.options.normalisation);
+            let unseeded_index = (0..self.words.len())
+                .find(|index| self.words[*index] == word && !word_indices.contains(index));
+
+            match unseeded_index {
+                Some(word_index) => word_indices.push(word_index),
+                None => {
+                    self.words.push(word);
+                    self.display.push(display);
+                    word_indices.push(self.words.len() - 1);
+                }
+            }
+        }
+
+        // Each seed is placed after one it crosses, and after any seed joining it to a seed beside
+        // it, whatever order they were given in, as the checks only let a word cross words
+        // already placed.
+        let mut layout = crossword::initialise_empty(&self.words);
+        let mut word_seeds = vec![None; self.words.len()];
+        for (order, (seed, word_index)) in seeds.iter().zip(&word_indices).enumerate() {
+            let cross_data = CrossData {
+                row: seed.row,
+                start_point: seed.start_point,
+                direction: seed.direction,
+                order,
+            };
+            if !cross_data.fits(&self.words[*word_index]) {
+                return Err(SeedError::Unplaceable(seed.word.clone()));
+            }
+            layout.words[*word_index].cross = Some(cross_data);
+            word_seeds[*word_index] = Some(seed);
+        }
+
+        let mut crossword = crossword::initialise_empty(&self.words);
+        for word_index in layout.get_crossing_order(0) {
+            let Some(seed) = word_seeds[word_index] else {
+                continue;
+            };
+            if !place_word(
+                word_index,
+                seed.row,
+                seed.start_point,
+                seed.direction,
+                &self.options,
+                &mut crossword,
+            ) {
+                return Err(SeedError::Unplaceable(seed.word.clone()));
+            }
+        }
+        if !crossword.is_connected() {
+            return Err(SeedError::Disconnected);
+        }
+
+        let seeds = word_indices
+            .iter()
+            .filter_map(|word_index| {
+                let cross_data = crossword.words[*word_index].cross.clone()?;
+                Some((*word_index, cross_data))
+            })
+            .collect();
+        self.seeds = seeds;
+
+        Ok(self)
+    }
+
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    pub fn options(&self) -> &SolverOptions {
+        &self.options
+    }
+
+    pub fn solve(&self) -> Solution<'_> {
+        let mut best_crosswords = vec![];
+
+        if self.words.is_empty() {
+            return Solution {
+                crosswords: best_crosswords,
+                exhaustive: true,
+                statistics: Statistics::default(),
+            };
+        }
+
+        let letter_map = letters::get_map(&self.words);
+        let search = Search::new(&letter_map, &self.options);
+        let mut crossword = self.initial_crossword();
+        let mut grid = Grid::new(&crossword);
+        let mut transpositions = Transpositions::new(self.options.transposition_limit);
+        // Words are only checked against the largest grid as they are inserted, so the first
+        // word and the seeds are checked here.
+        let fits = self.options.max_size.is_none_or(|max_size| {
+            let (width, height) = crossword.get_width_height();
+            max_size.fits(width, height)
+        });
+
+        match self.threads() {
+            _ if !fits => {}
+            _ if crossword.all_words_crossed() => {
+                if !self.options.block_overlaps || crossword.is_valid() {
+                    best_crosswords.push(crossword);
+                }
+            }
+            1 => options_compare(
+                &search,
+                &mut crossword,
+                &mut grid,
+                &mut transpositions,
+                &mut best_crosswords,
+            ),
+            threads => {
+                best_crosswords =
+                    options_compare_threaded(&search, &mut crossword, &mut grid, threads)
+            }
+        }
+
+        let mut crosswords = merge_crosswords(best_crosswords, &self.options);
+        // Which order the other words were placed in depends on the heuristics or on which thread
+        // found a crossword, so it is given again from the layout alone.
+        for crossword in &mut crosswords {
+            crossword.set_crossing_order(self.seeds.len());
+        }
+        let mut statistics = search.counters.statistics();
+        let best = match self.options.selection {
+            Selection::Best => crosswords.first(),
+            Selection::ParetoFront { .. } => crosswords.iter().min_by_key(|crossword| {
+                let (width, height) = crossword.get_width_height();
+                width * height
+            }),
+        };
+        statistics.best_size = best.map(Crossword::get_width_height);
+        if let Some(progress) = &self.options.progress {
+            (progress.callback)(&statistics);
+        }
+
+        let solution = Solution {
+            crosswords,
+            exhaustive: !search.is_stopped(),
+            statistics,
+        };
+        if let Some(observer) = &self.options.observer {
+            observer.finished(&solution);
+        }
+
+        solution
+    }
+
+    fn initial_crossword(&self) -> Crossword<'_> {
+        let mut crossword = if self.seeds.is_empty() {
+            crossword::initialise(&self.words)
+        } else {
+            let mut crossword = crossword::initialise_empty(&self.words);
+            for (word_index, cross_data) in &self.seeds {
+                crossword.words[*word_index].cross = Some(cross_data.clone());
+            }
+            crossword
+        };
+
+        for (word, clue) in crossword.words.iter_mut().zip(&self.clues) {
+            word.clue = clue.as_ref();
+        }
+        for (word, display) in crossword.words.iter_mut().zip(&self.display) {
+            word.display = display.as_deref();
+        }
+
+        crossword
+    }
+
+    fn threads(&self) -> usize {
+        match self.options.threads {
+            0 => thread::available_parallelism().map_or(1, usize::from),
+            threads => threads,
+        }
+    }
+}
+
+/// The cells of a word, and the word as given if it differs from them by more than case.
+fn normalise(word: &str, normalisation: &Normalisation) -> (String, Option<String>) {
+    let cells = letters::normalise(word, normalisation);
+    let display = (letters::to_display(&cells) != word.to_lowercase()).then(|| word.to_string());
+
+    (cells, display)
+}
+
+#[cfg(test)]
+#[path = "./tests_solver.rs"]
+mod tests_solver;
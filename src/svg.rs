@@ -0,0 +1,116 @@
+use std::fmt::Write;
+
+use crate::clues;
+use crate::crossword::Crossword;
+use crate::letters;
+
+/// How to draw a crossword as SVG. Sizes are in pixels.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SvgOptions {
+    pub cell_size: f64,
+    pub line_width: f64,
+    pub font_family: String,
+    pub number_font_size: f64,
+    pub letter_font_size: f64,
+    /// Fill in the solution letters, rather than leaving the grid blank.
+    pub show_letters: bool,
+}
+
+impl Default for SvgOptions {
+    fn default() -> SvgOptions {
+        SvgOptions {
+            cell_size: 32.0,
+            line_width: 1.0,
+            font_family: "Helvetica, Arial, sans-serif".to_string(),
+            number_font_size: 9.0,
+            letter_font_size: 20.0,
+            show_letters: false,
+        }
+    }
+}
+
+/// Draws a white square for every occupied cell, with clue numbers in the top left corner of
+/// the squares where words start.
+pub fn render(crossword: &Crossword, options: &SvgOptions) -> String {
+    let letters = crossword.get_letters();
+    let numbers = clues::numbering(crossword).numbers;
+    let cell = options.cell_size;
+    let margin = options.line_width / 2.0;
+
+    let (x_low, y_low, width, height) = if letters.is_empty() {
+        (0, 0, 0, 0)
+    } else {
+        let [x_low, y_low] = crossword.get_origin();
+        let (width, height) = crossword.get_width_height();
+        (x_low, y_low, width, height)
+    };
+    let svg_width = width as f64 * cell + options.line_width;
+    let svg_height = height as f64 * cell + options.line_width;
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}">"#,
+        svg_width, svg_height, svg_width, svg_height
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r#"<g font-family="{}" fill="black">"#,
+        escape(&options.font_family)
+    )
+    .unwrap();
+
+    for y in y_low..y_low + height as i32 {
+        for x in x_low..x_low + width as i32 {
+            let Some(letter) = letters.get(&[x, y]) else {
+                continue;
+            };
+            let left = (x - x_low) as f64 * cell + margin;
+            let top = (y - y_low) as f64 * cell + margin;
+
+            writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="white" stroke="black" stroke-width="{}"/>"#,
+                left, top, cell, cell, options.line_width
+            )
+            .unwrap();
+            if let Some(number) = numbers.get(&[x, y]) {
+                writeln!(
+                    svg,
+                    r#"<text x="{}" y="{}" font-size="{}">{}</text>"#,
+                    left + cell * 0.06,
+                    top + options.number_font_size,
+                    options.number_font_size,
+                    number
+                )
+                .unwrap();
+            }
+            if options.show_letters {
+                writeln!(
+                    svg,
+                    r#"<text x="{}" y="{}" font-size="{}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                    left + cell / 2.0,
+                    top + cell * 0.55,
+                    options.letter_font_size,
+                    escape(&letters::get_cell_text(*letter).to_uppercase())
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    svg.push_str("</g>\n</svg>\n");
+    svg
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+#[path = "./tests_svg.rs"]
+mod tests_svg;
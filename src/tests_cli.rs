@@ -0,0 +1,602 @@
+use crossword_compressor::{
+    CrossData, Crossword, Direction, WordCross, DEFAULT_TRANSPOSITION_LIMIT,
+};
+
+use std::env;
+
+use super::*;
+
+#[test]
+fn parse_positional_words() {
+    let args = helper_args(&["hereby", "exist", "words"]);
+
+    let arguments = parse(&args).unwrap();
+
+    assert!(!arguments.help);
+    assert_eq!(
+        WordSource::Arguments(helper_args(&["hereby", "exist", "words"])),
+        arguments.source
+    );
+}
+
+#[test]
+fn parse_words_file() {
+    let args = helper_args(&["--words-file", "words.txt"]);
+    assert_eq!(
+        WordSource::File("words.txt".to_string()),
+        parse(&args).unwrap().source
+    );
+
+    let args = helper_args(&["-f", "words.txt"]);
+    assert_eq!(
+        WordSource::File("words.txt".to_string()),
+        parse(&args).unwrap().source
+    );
+
+    let args = helper_args(&["--words-file"]);
+    assert!(parse(&args).is_err());
+
+    let args = helper_args(&["--words-file", "words.txt", "extra"]);
+    assert!(parse(&args).is_err());
+}
+
+#[test]
+fn parse_stdin() {
+    assert_eq!(WordSource::Stdin, parse(&[]).unwrap().source);
+    assert_eq!(
+        WordSource::Stdin,
+        parse(&helper_args(&["-"])).unwrap().source
+    );
+}
+
+#[test]
+fn parse_help_and_unknown_options() {
+    assert!(parse(&helper_args(&["--help"])).unwrap().help);
+    assert!(parse(&helper_args(&["-h", "word"])).unwrap().help);
+    assert!(parse(&helper_args(&["--unknown"])).is_err());
+}
+
+#[test]
+fn parse_block_overlaps() {
+    assert!(
+        !parse(&helper_args(&["word"]))
+            .unwrap()
+            .options
+            .block_overlaps
+    );
+    assert!(
+        parse(&helper_args(&["--block-overlaps", "word"]))
+            .unwrap()
+            .options
+            .block_overlaps
+    );
+}
+
+#[test]
+fn parse_normalisation() {
+    assert_eq!(
+        Normalisation::default(),
+        parse(&helper_args(&["word"]))
+            .unwrap()
+            .options
+            .normalisation
+    );
+
+    let args = helper_args(&[
+        "--strip-accents",
+        "--digraphs",
+        "LL, ch",
+        "--join-words",
+        "word",
+    ]);
+    assert_eq!(
+        Normalisation {
+            strip_diacritics: true,
+            digraphs: helper_args(&["ll", "ch"]),
+            drop_separators: true,
+        },
+        parse(&args).unwrap().options.normalisation
+    );
+
+    assert!(parse(&helper_args(&["--digraphs", "ll,xy", "word"])).is_err());
+    assert!(parse(&helper_args(&["--digraphs"])).is_err());
+}
+
+#[test]
+fn parse_progress() {
+    assert!(parse(&helper_args(&["word"]))
+        .unwrap()
+        .options
+        .progress
+        .is_none());
+
+    let progress = parse(&helper_args(&["--progress", "word"]))
+        .unwrap()
+        .options
+        .progress
+        .unwrap();
+    assert_eq!(Duration::from_secs(1), progress.interval);
+}
+
+#[test]
+fn parse_verbosity() {
+    let arguments = parse(&helper_args(&["word"])).unwrap();
+    assert_eq!(Verbosity::Normal, arguments.verbosity);
+    assert!(arguments.options.progress.is_none());
+
+    let arguments = parse(&helper_args(&["-q", "word"])).unwrap();
+    assert_eq!(Verbosity::Quiet, arguments.verbosity);
+
+    let arguments = parse(&helper_args(&["--verbose", "word"])).unwrap();
+    assert_eq!(Verbosity::Verbose, arguments.verbosity);
+    assert!(arguments.options.progress.is_some());
+}
+
+#[test]
+fn parse_threads() {
+    assert_eq!(1, parse(&helper_args(&["word"])).unwrap().options.threads);
+    assert_eq!(
+        4,
+        parse(&helper_args(&["--threads", "4", "word"]))
+            .unwrap()
+            .options
+            .threads
+    );
+    assert_eq!(
+        0,
+        parse(&helper_args(&["-j", "0"])).unwrap().options.threads
+    );
+    assert!(parse(&helper_args(&["--threads", "many"])).is_err());
+    assert!(parse(&helper_args(&["--threads"])).is_err());
+}
+
+#[test]
+fn parse_time_limit() {
+    assert_eq!(
+        None,
+        parse(&helper_args(&["word"])).unwrap().options.time_limit
+    );
+    assert_eq!(
+        Some(Duration::from_millis(1500)),
+        parse(&helper_args(&["--time-limit", "1.5"]))
+            .unwrap()
+            .options
+            .time_limit
+    );
+    assert!(parse(&helper_args(&["-t", "-1"])).is_err());
+    assert!(parse(&helper_args(&["-t", "soon"])).is_err());
+}
+
+#[test]
+fn test_parse_heuristics() {
+    assert_eq!(
+        Vec::<Heuristic>::new(),
+        parse(&helper_args(&["word"])).unwrap().options.heuristics
+    );
+    assert_eq!(
+        vec![Heuristic::Longest, Heuristic::Central],
+        parse(&helper_args(&["--heuristics", "longest, central", "word"]))
+            .unwrap()
+            .options
+            .heuristics
+    );
+    assert_eq!(
+        Ok(vec![Heuristic::Connectable]),
+        parse_heuristics("connectable")
+    );
+    assert!(parse_heuristics("shortest").is_err());
+    assert!(parse(&helper_args(&["--heuristics"])).is_err());
+}
+
+#[test]
+fn parse_transposition_limit() {
+    assert_eq!(
+        DEFAULT_TRANSPOSITION_LIMIT,
+        parse(&helper_args(&["word"]))
+            .unwrap()
+            .options
+            .transposition_limit
+    );
+    assert_eq!(
+        0,
+        parse(&helper_args(&["--transposition-limit", "0", "word"]))
+            .unwrap()
+            .options
+            .transposition_limit
+    );
+    assert!(parse(&helper_args(&["--transposition-limit", "-1"])).is_err());
+}
+
+#[test]
+fn test_parse_scorer() {
+    let crossword = helper_crossword();
+
+    assert_eq!(
+        BoundingBox.score(&crossword),
+        parse_scorer("bounding-box").unwrap().score(&crossword)
+    );
+    assert_eq!(3.0, parse_scorer("area").unwrap().score(&crossword));
+    assert_eq!(8.0, parse_scorer("perimeter").unwrap().score(&crossword));
+    assert_eq!(0.0, parse_scorer("aspect:3").unwrap().score(&crossword));
+    assert_eq!(-0.0, parse_scorer("crossings").unwrap().score(&crossword));
+    assert_eq!(-1.0, parse_scorer("density").unwrap().score(&crossword));
+    assert_eq!(
+        5.0,
+        parse_scorer("2*area, density").unwrap().score(&crossword)
+    );
+
+    assert!(parse_scorer("volume").is_err());
+    assert!(parse_scorer("aspect:0").is_err());
+    assert!(parse_scorer("-1*area").is_err());
+    assert!(parse(&helper_args(&["--scorer"])).is_err());
+    assert!(parse(&helper_args(&["--scorer", "area", "word"])).is_ok());
+}
+
+#[test]
+fn parse_pareto() {
+    assert_eq!(
+        Selection::Best,
+        parse(&helper_args(&["word"])).unwrap().options.selection
+    );
+    assert_eq!(
+        Selection::ParetoFront { crossings: false },
+        parse(&helper_args(&["--pareto"]))
+            .unwrap()
+            .options
+            .selection
+    );
+    assert_eq!(
+        Selection::ParetoFront { crossings: true },
+        parse(&helper_args(&["--pareto-crossings"]))
+            .unwrap()
+            .options
+            .selection
+    );
+}
+
+#[test]
+fn parse_max_size() {
+    assert_eq!(
+        None,
+        parse(&helper_args(&["word"])).unwrap().options.max_size
+    );
+    assert_eq!(
+        Some(MaxSize {
+            width: 21,
+            height: 13,
+            rotatable: false
+        }),
+        parse(&helper_args(&["--max-size", "21x13"]))
+            .unwrap()
+            .options
+            .max_size
+    );
+    assert_eq!(
+        Some(MaxSize {
+            width: 15,
+            height: 15,
+            rotatable: true
+        }),
+        parse(&helper_args(&["--rotatable", "-m", "15X15"]))
+            .unwrap()
+            .options
+            .max_size
+    );
+    assert!(parse(&helper_args(&["--max-size", "15"])).is_err());
+    assert!(parse(&helper_args(&["--max-size", "0x15"])).is_err());
+    assert!(parse(&helper_args(&["--max-size", "ax15"])).is_err());
+    assert!(parse(&helper_args(&["--rotatable"])).is_err());
+}
+
+#[test]
+fn parse_seeds() {
+    let arguments = parse(&helper_args(&[
+        "--seed",
+        "hereby:0:0:across",
+        "--seed",
+        "words:-2:1:D",
+        "--seeds-file",
+        "seeds.txt",
+    ]))
+    .unwrap();
+
+    assert_eq!(
+        vec![
+            Seed {
+                word: "hereby".to_string(),
+                row: 0,
+                start_point: 0,
+                direction: Direction::Across,
+            },
+            Seed {
+                word: "words".to_string(),
+                row: -2,
+                start_point: 1,
+                direction: Direction::Down,
+            },
+        ],
+        arguments.seeds
+    );
+    assert_eq!(Some("seeds.txt".to_string()), arguments.seeds_file);
+
+    assert!(parse(&helper_args(&["--seed", "hereby:0:0"])).is_err());
+    assert!(parse(&helper_args(&["--seed", "hereby:0:0:sideways"])).is_err());
+    assert!(parse(&helper_args(&["--seed", "hereby:zero:0:across"])).is_err());
+    assert!(parse(&helper_args(&["--seed", "ab:0:2147483647:across"])).is_err());
+    assert!(parse(&helper_args(&["--seed", "ab:2147483647:0:down"])).is_err());
+    assert!(parse(&helper_args(&["--seed"])).is_err());
+}
+
+#[test]
+fn test_read_seeds() {
+    let path = env::temp_dir().join(format!("seeds_{}.txt", std::process::id()));
+    fs::write(&path, "sample 2 0 down\n\nexist 5 2 a\n").unwrap();
+    let mut arguments = parse(&helper_args(&["--seed", "for:0:0:across"])).unwrap();
+    arguments.seeds_file = Some(path.to_string_lossy().to_string());
+
+    let seeds = read_seeds(&arguments);
+    fs::remove_file(&path).unwrap();
+
+    let seeds = seeds.unwrap();
+    assert_eq!(3, seeds.len());
+    assert_eq!("for", seeds[0].word);
+    assert_eq!(
+        Seed {
+            word: "exist".to_string(),
+            row: 5,
+            start_point: 2,
+            direction: Direction::Across,
+        },
+        seeds[2]
+    );
+
+    arguments.seeds_file = Some("/nonexistent/seeds.txt".to_string());
+    assert!(read_seeds(&arguments).is_err());
+}
+
+#[test]
+fn parse_json_and_load() {
+    let arguments = parse(&helper_args(&["--json", "out.json", "word"])).unwrap();
+    assert_eq!(Some("out.json".to_string()), arguments.json_file);
+    assert_eq!(None, arguments.load_file);
+
+    let arguments = parse(&helper_args(&["--load", "in.json"])).unwrap();
+    assert_eq!(Some("in.json".to_string()), arguments.load_file);
+
+    let arguments = parse(&helper_args(&["--ipuz", "out.ipuz", "word"])).unwrap();
+    assert_eq!(Some("out.ipuz".to_string()), arguments.ipuz_file);
+    assert!(parse(&helper_args(&["--ipuz"])).is_err());
+
+    let arguments = parse(&helper_args(&["--puz", "out.puz", "word"])).unwrap();
+    assert_eq!(Some("out.puz".to_string()), arguments.puz_file);
+    assert!(parse(&helper_args(&["--puz"])).is_err());
+
+    assert!(parse(&helper_args(&["--json"])).is_err());
+    assert!(parse(&helper_args(&["--load", "in.json", "word"])).is_err());
+    assert!(parse(&helper_args(&["--load", "in.json", "--seed", "for:0:0:a"])).is_err());
+}
+
+#[test]
+fn write_and_read_crosswords() {
+    let path = env::temp_dir().join(format!("crosswords_{}.json", std::process::id()));
+    let path = path.to_string_lossy().to_string();
+    let crossword = helper_crossword();
+
+    write_crosswords(&path, std::slice::from_ref(&crossword)).unwrap();
+    let loaded = read_crosswords(&path, &SolverOptions::default());
+    fs::remove_file(&path).unwrap();
+
+    let loaded = loaded.unwrap();
+    assert_eq!(1, loaded.len());
+    assert_eq!(crossword, loaded[0].crossword());
+
+    assert!(read_crosswords("/nonexistent/crosswords.json", &SolverOptions::default()).is_err());
+    assert!(write_crosswords("/nonexistent/crosswords.json", &[]).is_err());
+}
+
+#[test]
+fn parse_svg() {
+    let arguments = parse(&helper_args(&["word"])).unwrap();
+    assert_eq!(None, arguments.svg_file);
+    assert_eq!(SvgOptions::default(), arguments.svg_options);
+
+    let arguments = parse(&helper_args(&[
+        "--svg",
+        "grid.svg",
+        "--svg-letters",
+        "--cell-size",
+        "64",
+        "--font",
+        "Georgia",
+        "word",
+    ]))
+    .unwrap();
+    assert_eq!(Some("grid.svg".to_string()), arguments.svg_file);
+    assert!(arguments.svg_options.show_letters);
+    assert_eq!(64.0, arguments.svg_options.cell_size);
+    assert_eq!(40.0, arguments.svg_options.letter_font_size);
+    assert_eq!("Georgia", arguments.svg_options.font_family);
+
+    assert!(parse(&helper_args(&["--svg"])).is_err());
+    assert!(parse(&helper_args(&["--cell-size", "0"])).is_err());
+    assert!(parse(&helper_args(&["--cell-size", "big"])).is_err());
+    assert!(parse(&helper_args(&["--font"])).is_err());
+}
+
+#[test]
+fn test_write_files() {
+    let directory = env::temp_dir();
+    let path = directory.join(format!("grid_{}.svg", std::process::id()));
+    let crosswords = [helper_crossword(), helper_crossword()];
+
+    write_files(&path.to_string_lossy(), &crosswords, |crossword| {
+        Ok(crossword.words[0].word.to_string())
+    })
+    .unwrap();
+
+    for number in 1..=2 {
+        let numbered = directory.join(format!("grid_{}-{}.svg", std::process::id(), number));
+        assert_eq!("for", fs::read_to_string(&numbered).unwrap());
+        fs::remove_file(&numbered).unwrap();
+    }
+    assert!(!path.exists());
+
+    assert!(write_files("/nonexistent/grid.svg", &crosswords[..1], |_| Ok("")).is_err());
+    assert!(write_files(&path.to_string_lossy(), &crosswords[..1], |_| {
+        Err::<String, _>("cannot render".to_string())
+    })
+    .is_err());
+    assert!(!path.exists());
+}
+
+#[test]
+fn read_ipuz_crosswords_and_seeds() {
+    let path = env::temp_dir().join(format!("puzzle_{}.IPUZ", std::process::id()));
+    let words = ["hereby".to_string(), "exist".to_string()];
+    let mut crossword = crossword_compressor::crossword::initialise(&words);
+    crossword.words[1].cross = Some(CrossData {
+        row: 1,
+        start_point: 0,
+        direction: Direction::Down,
+        order: 1,
+    });
+    fs::write(&path, ipuz::to_ipuz(&crossword)).unwrap();
+    let mut arguments = parse(&helper_args(&["--seed", "for:0:4:down"])).unwrap();
+    arguments.seeds_file = Some(path.to_string_lossy().to_string());
+
+    let loaded = read_crosswords(&path.to_string_lossy(), &SolverOptions::default());
+    let seeds = read_seeds(&arguments);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(words.to_vec(), loaded.unwrap()[0].words);
+    let seeds = seeds.unwrap();
+    assert_eq!(
+        vec!["for", "hereby", "exist"],
+        seeds
+            .iter()
+            .map(|seed| seed.word.as_str())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        Seed {
+            word: "exist".to_string(),
+            row: 1,
+            start_point: 0,
+            direction: Direction::Down,
+        },
+        seeds[2]
+    );
+}
+
+#[test]
+fn test_numbered_path() {
+    assert_eq!("out/grid-2.svg", numbered_path("out/grid.svg", 2));
+    assert_eq!("grid-1", numbered_path("grid", 1));
+}
+
+#[test]
+fn test_split_lines() {
+    assert_eq!(
+        helper_args(&["hereby", "exist", "words"]),
+        split_lines("hereby\n  exist \n\n\r\nwords\r\n")
+    );
+}
+
+#[test]
+fn test_check_words() {
+    let normalisation = Normalisation::default();
+    assert!(check_words(&helper_args(&["sample", "output"]), &normalisation).is_ok());
+    assert!(check_words(&[], &normalisation).is_err());
+    assert!(check_words(&helper_args(&["sample", "-"]), &normalisation).is_err());
+    assert!(check_words(&helper_args(&["two words"]), &normalisation).is_err());
+
+    let normalisation = Normalisation {
+        drop_separators: true,
+        ..Normalisation::default()
+    };
+    assert!(check_words(&helper_args(&["two words"]), &normalisation).is_ok());
+    assert!(check_words(&helper_args(&["' -"]), &normalisation).is_err());
+}
+
+#[test]
+fn read_words_with_clues() {
+    let path = env::temp_dir().join(format!("clues_{}.txt", std::process::id()));
+    fs::write(&path, "hereby\tIn this way\nexist\n").unwrap();
+
+    let words = read_words(
+        &WordSource::File(path.to_string_lossy().to_string()),
+        &Normalisation::default(),
+    );
+    fs::remove_file(&path).unwrap();
+
+    let (words, clues) = words.unwrap();
+    assert_eq!(helper_args(&["hereby", "exist"]), words);
+    assert_eq!(
+        vec![
+            Some(Clue {
+                text: "In this way".to_string(),
+                enumeration: None,
+            }),
+            None,
+        ],
+        clues
+    );
+}
+
+#[test]
+fn test_split_clue() {
+    assert_eq!(("exist".to_string(), None), split_clue("exist"));
+    assert_eq!(("exist".to_string(), None), split_clue("exist\t "));
+    assert_eq!(
+        (
+            "redwine".to_string(),
+            Some(Clue {
+                text: "Claret, say".to_string(),
+                enumeration: Some("(3,4)".to_string()),
+            })
+        ),
+        split_clue("redwine\tClaret, say \t(3,4)")
+    );
+    assert_eq!(
+        (
+            "hereby".to_string(),
+            Some(Clue {
+                text: String::new(),
+                enumeration: Some("(6)".to_string()),
+            })
+        ),
+        split_clue("hereby\t\t(6)")
+    );
+}
+
+#[test]
+fn read_words_from_arguments() {
+    let source = WordSource::Arguments(helper_args(&["for", "sample"]));
+    assert_eq!(
+        (helper_args(&["for", "sample"]), vec![None, None]),
+        read_words(&source, &Normalisation::default()).unwrap()
+    );
+
+    let source = WordSource::File("/nonexistent/words.txt".to_string());
+    assert!(read_words(&source, &Normalisation::default()).is_err());
+}
+
+fn helper_crossword() -> Crossword<'static> {
+    Crossword {
+        words: vec![WordCross {
+            word: "for",
+            clue: None,
+            display: None,
+            cross: Some(CrossData {
+                row: 0,
+                start_point: 0,
+                direction: Direction::Across,
+                order: 0,
+            }),
+        }],
+    }
+}
+
+fn helper_args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|x| x.to_string()).collect()
+}
@@ -0,0 +1,140 @@
+use crate::crossword::WordCross;
+use crate::letters::{self, Normalisation};
+use crate::tests_helpers::helper_word;
+
+use super::*;
+
+#[test]
+fn test_numbering() {
+    let crossword = Crossword {
+        words: vec![
+            helper_word("hereby", 0, 0, Direction::Across),
+            helper_word("exist", 1, 0, Direction::Down),
+            helper_word("words", 2, -2, Direction::Down),
+            helper_word("sample", 3, 1, Direction::Across),
+            helper_word("hi", 0, 0, Direction::Down),
+            WordCross {
+                word: "unused",
+                clue: None,
+                display: None,
+                cross: None,
+            },
+        ],
+    };
+
+    let numbering = numbering(&crossword);
+
+    assert_eq!(
+        vec![
+            Entry {
+                number: 2,
+                word_index: 0,
+                word: "hereby",
+            },
+            Entry {
+                number: 4,
+                word_index: 3,
+                word: "sample",
+            },
+        ],
+        numbering.across
+    );
+    assert_eq!(
+        vec![
+            Entry {
+                number: 1,
+                word_index: 2,
+                word: "words",
+            },
+            Entry {
+                number: 2,
+                word_index: 4,
+                word: "hi",
+            },
+            Entry {
+                number: 3,
+                word_index: 1,
+                word: "exist",
+            },
+        ],
+        numbering.down
+    );
+    assert_eq!(4, numbering.numbers.len());
+    assert_eq!(Some(&1), numbering.numbers.get(&[2, -2]));
+    assert_eq!(Some(&2), numbering.numbers.get(&[0, 0]));
+    assert_eq!(Some(&3), numbering.numbers.get(&[1, 0]));
+    assert_eq!(Some(&4), numbering.numbers.get(&[1, 3]));
+}
+
+#[test]
+fn numbering_empty_crossword() {
+    let crossword = Crossword { words: vec![] };
+
+    assert_eq!(Numbering::default(), numbering(&crossword));
+}
+
+#[test]
+fn clue_get_enumeration() {
+    let clue = Clue {
+        text: "Claret, say".to_string(),
+        enumeration: Some("(3,4)".to_string()),
+    };
+    assert_eq!(
+        "(3,4)",
+        clue.get_enumeration(&helper_word("redwine", 0, 0, Direction::Across))
+    );
+    assert_eq!(
+        "(4)",
+        Clue::default().get_enumeration(&helper_word("café", 0, 0, Direction::Across))
+    );
+}
+
+#[test]
+fn clue_get_enumeration_from_display() {
+    let normalisation = Normalisation {
+        digraphs: vec!["ll".to_string()],
+        drop_separators: true,
+        ..Normalisation::default()
+    };
+    let cases = [
+        ("Ice cream", "(3,5)"),
+        ("jack-in-the-box", "(4-2-3-3)"),
+        ("bread 'n' butter", "(5,1,6)"),
+        ("Llanelli", "(6)"),
+        ("Pwll - y - Wrach", "(3-1-5)"),
+    ];
+
+    for (display, expected) in cases {
+        let cells = letters::normalise(display, &normalisation);
+        let mut word = helper_word(&cells, 0, 0, Direction::Across);
+        word.display = Some(display);
+
+        assert_eq!(
+            expected,
+            Clue::default().get_enumeration(&word),
+            "{}",
+            display
+        );
+    }
+}
+
+#[test]
+fn test_format_clue_lists() {
+    let clue = Clue {
+        text: "In this way".to_string(),
+        enumeration: None,
+    };
+    let mut crossword = Crossword {
+        words: vec![
+            helper_word("hereby", 0, 0, Direction::Across),
+            helper_word("exist", 1, 0, Direction::Down),
+        ],
+    };
+    crossword.words[0].clue = Some(&clue);
+
+    assert_eq!(
+        "Across\n1. In this way (6)\nDown\n2. (5)\n",
+        format_clue_lists(&crossword)
+    );
+    assert_eq!("", format_clue_lists(&Crossword { words: vec![] }));
+}
@@ -1,4 +1,5 @@
 use super::*;
+use crate::tests_helpers::helper_word;
 
 #[test]
 fn direction_index() {
@@ -30,6 +31,28 @@ fn cross_data_get_position() {
     assert_eq!([28, -100], cross_data.get_position());
 }
 
+#[test]
+fn cross_data_fits() {
+    let cross_data = CrossData {
+        row: i32::MAX - 1,
+        start_point: i32::MAX - 3,
+        direction: Direction::Across,
+        order: 0,
+    };
+    assert!(cross_data.fits("abc"));
+    assert!(!cross_data.fits("abcd"));
+    assert!(!CrossData {
+        row: i32::MAX,
+        ..cross_data.clone()
+    }
+    .fits("ab"));
+    assert!(!CrossData {
+        start_point: i32::MIN,
+        ..cross_data
+    }
+    .fits("ab"));
+}
+
 #[test]
 fn crossword_get_crossable_letters() {
     let crossword = helper_get_generic_crossword();
@@ -62,6 +85,9 @@ fn crossword_get_crossable_letters() {
 fn crossword_get_next_order() {
     let crossword = helper_get_generic_crossword();
     assert_eq!(3, crossword.get_next_order());
+
+    let words = vec!["alpha".to_string()];
+    assert_eq!(0, initialise_empty(&words).get_next_order());
 }
 
 #[test]
@@ -70,6 +96,55 @@ fn crossword_get_min_max() {
     assert_eq!((5, 7), crossword.get_min_max());
 }
 
+#[test]
+fn crossword_get_origin() {
+    let crossword = helper_get_generic_crossword();
+    assert_eq!([1, 6], crossword.get_origin());
+}
+
+#[test]
+fn crossword_get_width_height() {
+    let crossword = helper_get_generic_crossword();
+    assert_eq!((7, 5), crossword.get_width_height());
+}
+
+#[test]
+fn crossword_get_size_bounds() {
+    let mut crossword = helper_get_generic_crossword();
+    assert_eq!(vec![(7, 5)], crossword.get_size_bounds());
+
+    crossword.words[2].word = "charlotte";
+    assert_eq!(vec![(9, 5), (7, 9)], crossword.get_size_bounds());
+
+    crossword.words[2].cross = Some(CrossData {
+        row: 7,
+        start_point: 2,
+        direction: Direction::Down,
+        order: 3,
+    });
+    assert_eq!(vec![(7, 9)], crossword.get_size_bounds());
+}
+
+#[test]
+fn crossword_count_cells_and_crossings() {
+    let crossword = helper_get_generic_crossword();
+    assert_eq!(13, crossword.count_cells());
+    assert_eq!(2, crossword.count_crossings());
+    assert_eq!(7, crossword.count_uncrossed_letters());
+}
+
+#[test]
+fn crossword_get_letters() {
+    let crossword = helper_get_generic_crossword();
+    let letters = crossword.get_letters();
+
+    assert_eq!(13, letters.len());
+    assert_eq!(Some(&'b'), letters.get(&[3, 6]));
+    assert_eq!(Some(&'a'), letters.get(&[5, 6]));
+    assert_eq!(Some(&'p'), letters.get(&[5, 8]));
+    assert_eq!(None, letters.get(&[0, 0]));
+}
+
 #[test]
 fn crossword_all_words_crossed() {
     let mut crossword = helper_get_generic_crossword();
@@ -96,6 +171,80 @@ fn crossword_all_words_crossed() {
     assert!(crossword.all_words_crossed());
 }
 
+#[test]
+fn crossword_is_valid() {
+    let mut crossword = helper_get_generic_crossword();
+    assert!(crossword.is_valid());
+
+    crossword.words[2].cross = Some(CrossData {
+        row: 7,
+        start_point: 6,
+        direction: Direction::Across,
+        order: 3,
+    });
+    assert!(!crossword.is_valid());
+
+    crossword.words[2].cross = Some(CrossData {
+        row: 4,
+        start_point: 1,
+        direction: Direction::Down,
+        order: 3,
+    });
+    assert!(!crossword.is_valid());
+
+    crossword.words[2].cross = Some(CrossData {
+        row: 8,
+        start_point: 6,
+        direction: Direction::Across,
+        order: 3,
+    });
+    assert!(!crossword.is_valid());
+}
+
+#[test]
+fn crossword_is_connected() {
+    let mut crossword = helper_get_generic_crossword();
+    assert!(crossword.is_connected());
+
+    crossword.words[2].cross = Some(CrossData {
+        row: 0,
+        start_point: 0,
+        direction: Direction::Across,
+        order: 3,
+    });
+    assert!(!crossword.is_connected());
+
+    crossword.words[2].cross = Some(CrossData {
+        row: 4,
+        start_point: 4,
+        direction: Direction::Down,
+        order: 3,
+    });
+    assert!(crossword.is_connected());
+}
+
+#[test]
+fn crossword_get_crossing_order() {
+    let crossword = helper_get_generic_crossword();
+    assert_eq!(vec![1, 0, 3], crossword.get_crossing_order(0));
+
+    // "rstu" crosses "fnopqt" but lies beside "jklm", so it has to wait for "mr", which joins them.
+    let mut crossword = helper_get_joined_crossword();
+    for (order, word) in [0, 3, 1, 4, 5, 2].into_iter().zip(&mut crossword.words) {
+        word.cross.as_mut().unwrap().order = order;
+    }
+    assert_eq!(vec![0, 2, 1, 3, 4, 5], crossword.get_crossing_order(0));
+    assert_eq!(vec![0, 2, 5, 1, 4, 3], crossword.get_crossing_order(3));
+
+    crossword.set_crossing_order(0);
+    let orders: Vec<usize> = crossword
+        .words
+        .iter()
+        .map(|word| word.cross.as_ref().unwrap().order)
+        .collect();
+    assert_eq!(vec![0, 2, 1, 3, 4, 5], orders);
+}
+
 #[test]
 fn crossword_get_x_y_width() {
     let crossword = helper_get_generic_crossword();
@@ -107,6 +256,8 @@ fn helper_get_generic_crossword() -> Crossword<'static> {
         words: vec![
             WordCross {
                 word: "alpha",
+                clue: None,
+                display: None,
                 cross: Some(CrossData {
                     row: 5,
                     start_point: 6,
@@ -116,6 +267,8 @@ fn helper_get_generic_crossword() -> Crossword<'static> {
             },
             WordCross {
                 word: "bravo",
+                clue: None,
+                display: None,
                 cross: Some(CrossData {
                     row: 6,
                     start_point: 3,
@@ -125,10 +278,14 @@ fn helper_get_generic_crossword() -> Crossword<'static> {
             },
             WordCross {
                 word: "charlie",
+                clue: None,
+                display: None,
                 cross: None,
             },
             WordCross {
                 word: "delta",
+                clue: None,
+                display: None,
                 cross: Some(CrossData {
                     row: 10,
                     start_point: 1,
@@ -141,6 +298,110 @@ fn helper_get_generic_crossword() -> Crossword<'static> {
     return crossword;
 }
 
+/// The grid below, where "jklm" and "rstu" lie in neighbouring rows, joined by "mr".
+///
+/// ```text
+///  bcdef
+///  g   n
+///  h   o
+///  i   p
+/// jklm q
+///    rstu
+/// ```
+fn helper_get_joined_crossword() -> Crossword<'static> {
+    Crossword {
+        words: vec![
+            helper_word("bcdef", 0, 1, Direction::Across),
+            helper_word("fnopqt", 5, 0, Direction::Down),
+            helper_word("bghik", 1, 0, Direction::Down),
+            helper_word("jklm", 4, 0, Direction::Across),
+            helper_word("mr", 3, 4, Direction::Down),
+            helper_word("rstu", 5, 3, Direction::Across),
+        ],
+    }
+}
+
+#[test]
+fn crossword_get_canonical_form() {
+    let crossword = helper_get_generic_crossword();
+    let form = crossword.get_canonical_form();
+
+    let mut moved = crossword.clone();
+    for word in &mut moved.words {
+        if let Some(cross_data) = &mut word.cross {
+            let [x, y] = cross_data.get_position();
+            let [x, y] = [x - 4, y + 7];
+            (cross_data.row, cross_data.start_point) = match cross_data.direction {
+                Direction::Across => (y, x),
+                Direction::Down => (x, y),
+            };
+        }
+    }
+    assert_eq!(form, moved.get_canonical_form());
+
+    let mut transposed = crossword.clone();
+    for word in &mut transposed.words {
+        if let Some(cross_data) = &mut word.cross {
+            cross_data.direction = cross_data.direction.change();
+        }
+    }
+    assert_ne!(crossword.get_letters(), transposed.get_letters());
+    assert_eq!(form, transposed.get_canonical_form());
+
+    let mut reordered = crossword.clone();
+    reordered.words.reverse();
+    for (order, word) in reordered.words.iter_mut().rev().enumerate() {
+        if let Some(cross_data) = &mut word.cross {
+            cross_data.order = order;
+        }
+    }
+    assert_eq!(form, reordered.get_canonical_form());
+
+    let mut other = crossword.clone();
+    other.words[3].cross.as_mut().unwrap().start_point += 1;
+    assert_ne!(form, other.get_canonical_form());
+
+    let empty = Crossword { words: vec![] };
+    assert_eq!(CanonicalForm(vec![]), empty.get_canonical_form());
+}
+
+#[test]
+fn test_initialise() {
+    let words = vec!["alpha".to_string(), "bravo".to_string()];
+
+    let crossword = initialise(&words);
+    assert_eq!(
+        Some(CrossData {
+            row: 0,
+            start_point: 0,
+            direction: Direction::Across,
+            order: 0,
+        }),
+        crossword.words[0].cross
+    );
+    assert_eq!(None, crossword.words[1].cross);
+
+    let crossword = initialise_empty(&words);
+    assert_eq!("alpha", crossword.words[0].word);
+    assert_eq!(None, crossword.words[0].cross);
+    assert_eq!(None, crossword.words[1].cross);
+}
+
+#[test]
+fn test_get_cells() {
+    let cross_data = CrossData {
+        row: 3,
+        start_point: -1,
+        direction: Direction::Down,
+        order: 0,
+    };
+    assert_eq!(vec![[3, -1], [3, 0], [3, 1]], get_cells("fox", &cross_data));
+    assert_eq!(
+        vec![[3, -1], [3, 0], [3, 1], [3, 2]],
+        get_cells("café", &cross_data)
+    );
+}
+
 #[test]
 fn test_get_position_end() {
     assert_eq!(
@@ -167,4 +428,59 @@ fn test_get_position_end() {
             }
         )
     );
+    assert_eq!(
+        [5, 0],
+        get_position_end(
+            "straße",
+            &CrossData {
+                row: 0,
+                start_point: 0,
+                direction: Direction::Across,
+                order: 0
+            }
+        )
+    );
+}
+
+#[test]
+fn crossword_non_ascii_words() {
+    let crossword = Crossword {
+        words: vec![
+            WordCross {
+                word: "μέλι",
+                clue: None,
+                display: None,
+                cross: Some(CrossData {
+                    row: 0,
+                    start_point: 0,
+                    direction: Direction::Across,
+                    order: 0,
+                }),
+            },
+            WordCross {
+                word: "λόγος",
+                clue: None,
+                display: None,
+                cross: Some(CrossData {
+                    row: 2,
+                    start_point: 0,
+                    direction: Direction::Down,
+                    order: 1,
+                }),
+            },
+            WordCross {
+                word: "дом",
+                clue: None,
+                display: None,
+                cross: None,
+            },
+        ],
+    };
+
+    assert_eq!((4, 5), crossword.get_width_height());
+    assert_eq!(8, crossword.count_cells());
+    assert_eq!(1, crossword.count_crossings());
+    assert_eq!(3, crossword.count_uncrossed_letters());
+    assert!(crossword.is_valid());
+    assert_eq!("μέλι\n  ό \n  γ \n  ο \n  ς \n", crossword.to_string());
 }
@@ -0,0 +1,309 @@
+use crate::crossword::WordCross;
+use crate::solver::{Selection, Solver};
+
+use super::*;
+
+#[test]
+fn crossword_to_json() {
+    let words = ["for".to_string(), "of".to_string(), "unused".to_string()];
+    let mut crossword = crossword::initialise(&words);
+    assert!(place_word(
+        1,
+        0,
+        -1,
+        Direction::Down,
+        &SolverOptions::default(),
+        &mut crossword
+    ));
+
+    let expected = r#"{
+  "words": [
+    {
+      "word": "for",
+      "cross": {
+        "row": 0,
+        "start_point": 0,
+        "direction": "across",
+        "order": 0,
+        "x": 0,
+        "y": 0
+      }
+    },
+    {
+      "word": "of",
+      "cross": {
+        "row": 0,
+        "start_point": -1,
+        "direction": "down",
+        "order": 1,
+        "x": 0,
+        "y": -1
+      }
+    },
+    {
+      "word": "unused",
+      "cross": null
+    }
+  ],
+  "bounding_box": {
+    "x": 0,
+    "y": -1,
+    "width": 3,
+    "height": 2
+  }
+}
+"#;
+    assert_eq!(expected, to_json(&crossword));
+}
+
+#[test]
+fn empty_crossword_to_json() {
+    let crossword = Crossword { words: vec![] };
+
+    assert_eq!(
+        "{\n  \"words\": [],\n  \"bounding_box\": null\n}\n",
+        to_json(&crossword)
+    );
+}
+
+#[test]
+fn solutions_round_trip() {
+    let solver = Solver::new(
+        &["hereby", "exist", "words", "for", "sample", "output"],
+        SolverOptions::default(),
+    );
+    let crosswords = solver.solve().crosswords;
+    assert!(!crosswords.is_empty());
+
+    let loaded = from_json(&to_json_list(&crosswords), &SolverOptions::default()).unwrap();
+
+    assert_eq!(crosswords.len(), loaded.len());
+    for (crossword, loaded) in crosswords.iter().zip(&loaded) {
+        assert_eq!(*crossword, loaded.crossword());
+        assert_eq!(to_json(crossword), to_json(&loaded.crossword()));
+    }
+}
+
+#[test]
+fn joined_rows_round_trip() {
+    // Some of these crosswords have words in neighbouring rows, which have to be saved in an order
+    // that places the word joining them first.
+    let options = SolverOptions {
+        selection: Selection::ParetoFront { crossings: false },
+        ..SolverOptions::default()
+    };
+    let solver = Solver::new(
+        &[
+            "iateoe", "tt", "earor", "etiii", "arios", "ia", "aso", "sttas",
+        ],
+        options,
+    );
+    let crosswords = solver.solve().crosswords;
+    assert!(!crosswords.is_empty());
+
+    let loaded = from_json(&to_json_list(&crosswords), &SolverOptions::default()).unwrap();
+
+    assert_eq!(crosswords.len(), loaded.len());
+    for (crossword, loaded) in crosswords.iter().zip(&loaded) {
+        assert_eq!(*crossword, loaded.crossword());
+    }
+}
+
+#[test]
+fn clues_round_trip() {
+    let clue = Clue {
+        text: "Be".to_string(),
+        enumeration: Some("(5)".to_string()),
+    };
+    let words = ["hereby".to_string(), "exist".to_string()];
+    let mut crossword = crossword::initialise(&words);
+    crossword.words[1].clue = Some(&clue);
+    assert!(place_word(
+        1,
+        1,
+        0,
+        Direction::Down,
+        &SolverOptions::default(),
+        &mut crossword
+    ));
+
+    let text = to_json(&crossword);
+    let loaded = from_json(&text, &SolverOptions::default()).unwrap();
+
+    assert!(text.contains("\"clue\": \"Be\",\n      \"enumeration\": \"(5)\","));
+    assert_eq!(vec![None, Some(clue.clone())], loaded[0].clues);
+    assert_eq!(crossword, loaded[0].crossword());
+}
+
+#[test]
+fn normalised_words_round_trip() {
+    let mut options = SolverOptions::default();
+    options.normalisation.digraphs = vec!["ll".to_string()];
+    options.normalisation.drop_separators = true;
+    let solver = Solver::new(&["Pwll Du", "llan"], options.clone());
+    let solution = solver.solve();
+    assert!(!solution.crosswords.is_empty());
+
+    for crossword in &solution.crosswords {
+        let text = to_json(crossword);
+        let loaded = from_json(&text, &options).unwrap();
+
+        assert!(text.contains("\"word\": \"pwlldu\",\n      \"display\": \"Pwll Du\","));
+        assert!(text.contains("\"word\": \"llan\",\n      \"cross\""));
+        assert_eq!(solver.words(), loaded[0].words);
+        assert_eq!(vec![Some("Pwll Du".to_string()), None], loaded[0].display);
+        assert_eq!(*crossword, loaded[0].crossword());
+    }
+}
+
+#[test]
+fn from_json_single_object() {
+    let text = r#"{"words": [
+        {"word": "of", "cross": {"row": 0, "start_point": -1, "direction": "down", "order": 1}},
+        {"word": "for", "cross": {"row": 0, "start_point": 0, "direction": "across", "order": 0}},
+        {"word": "unused"}
+    ]}"#;
+
+    let loaded = from_json(text, &SolverOptions::default()).unwrap();
+
+    assert_eq!(1, loaded.len());
+    assert_eq!(
+        Crossword {
+            words: vec![
+                WordCross {
+                    word: "of",
+                    clue: None,
+                    display: None,
+                    cross: Some(CrossData {
+                        row: 0,
+                        start_point: -1,
+                        direction: Direction::Down,
+                        order: 1,
+                    }),
+                },
+                WordCross {
+                    word: "for",
+                    clue: None,
+                    display: None,
+                    cross: Some(CrossData {
+                        row: 0,
+                        start_point: 0,
+                        direction: Direction::Across,
+                        order: 0,
+                    }),
+                },
+                WordCross {
+                    word: "unused",
+                    clue: None,
+                    display: None,
+                    cross: None,
+                },
+            ],
+        },
+        loaded[0].crossword()
+    );
+}
+
+#[test]
+fn from_json_schema_errors() {
+    let options = SolverOptions::default();
+
+    assert!(matches!(
+        from_json("{\"words\": [", &options),
+        Err(LoadError::Syntax(_))
+    ));
+    assert!(matches!(
+        from_json("{}", &options),
+        Err(LoadError::Schema(_))
+    ));
+    assert!(matches!(
+        from_json(r#"{"words": [{"word": ""}]}"#, &options),
+        Err(LoadError::Schema(_))
+    ));
+    assert!(matches!(
+        from_json(
+            r#"{"words": [{"word": "for", "cross": {"row": 0, "start_point": 0, "direction": "sideways", "order": 0}}]}"#,
+            &options
+        ),
+        Err(LoadError::Schema(_))
+    ));
+    assert!(matches!(
+        from_json(
+            r#"{"words": [{"word": "for", "cross": {"row": 0.5, "start_point": 0, "direction": "down", "order": 0}}]}"#,
+            &options
+        ),
+        Err(LoadError::Schema(_))
+    ));
+    assert!(matches!(
+        from_json(
+            r#"{"words": [{"word": "for", "cross": {"row": 0, "start_point": 0, "direction": "down", "order": -1}}]}"#,
+            &options
+        ),
+        Err(LoadError::Schema(_))
+    ));
+    assert!(matches!(
+        from_json(
+            r#"{"words": [{"word": "for", "cross": {"row": 0, "start_point": 0, "direction": "down", "order": 1e300}}]}"#,
+            &options
+        ),
+        Err(LoadError::Schema(_))
+    ));
+    assert!(matches!(
+        from_json(
+            r#"{"words": [{"word": "for", "cross": {"row": 0, "start_point": 2147483646, "direction": "across", "order": 0}}]}"#,
+            &options
+        ),
+        Err(LoadError::Schema(_))
+    ));
+}
+
+#[test]
+fn from_json_layout_errors() {
+    let options = SolverOptions::default();
+
+    let clashing = r#"{"words": [
+        {"word": "for", "cross": {"row": 0, "start_point": 0, "direction": "across", "order": 0}},
+        {"word": "of", "cross": {"row": 0, "start_point": 0, "direction": "down", "order": 1}}
+    ]}"#;
+    assert!(matches!(
+        from_json(clashing, &options),
+        Err(LoadError::Layout(_))
+    ));
+
+    let disconnected = r#"{"words": [
+        {"word": "for", "cross": {"row": 0, "start_point": 0, "direction": "across", "order": 0}},
+        {"word": "of", "cross": {"row": 5, "start_point": 5, "direction": "down", "order": 1}}
+    ]}"#;
+    assert!(matches!(
+        from_json(disconnected, &options),
+        Err(LoadError::Layout(_))
+    ));
+}
+
+#[test]
+fn loaded_crossword_seeds() {
+    let text = r#"{"words": [
+        {"word": "of", "cross": {"row": 0, "start_point": -1, "direction": "down", "order": 1}},
+        {"word": "for", "cross": {"row": 0, "start_point": 0, "direction": "across", "order": 0}},
+        {"word": "unused"}
+    ]}"#;
+    let loaded = from_json(text, &SolverOptions::default()).unwrap();
+
+    assert_eq!(
+        vec![
+            Seed {
+                word: "for".to_string(),
+                row: 0,
+                start_point: 0,
+                direction: Direction::Across,
+            },
+            Seed {
+                word: "of".to_string(),
+                row: 0,
+                start_point: -1,
+                direction: Direction::Down,
+            },
+        ],
+        loaded[0].seeds()
+    );
+}
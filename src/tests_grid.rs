@@ -0,0 +1,128 @@
+use super::*;
+
+#[test]
+fn grid_new() {
+    let words = vec!["café".to_string(), "fig".to_string(), "pear".to_string()];
+    let mut crossword = crate::crossword::initialise(&words);
+    crossword.words[1].cross = Some(CrossData {
+        row: 2,
+        start_point: 0,
+        direction: Direction::Down,
+        order: 1,
+    });
+
+    let grid = Grid::new(&crossword);
+
+    assert_eq!(Some('c'), grid.letter([0, 0]));
+    assert_eq!(Some('é'), grid.letter([3, 0]));
+    assert_eq!(None, grid.letter([4, 0]));
+    assert_eq!(Some('g'), grid.letter([2, 2]));
+    assert!(grid.has_word([2, 0], Direction::Across));
+    assert!(grid.has_word([2, 0], Direction::Down));
+    assert!(!grid.has_word([2, 1], Direction::Across));
+    assert!(!grid.has_word([0, 1], Direction::Down));
+    assert_eq!(crossword.get_next_order(), grid.next_order());
+}
+
+#[test]
+fn grid_insert_remove() {
+    let words = vec!["start".to_string(), "tall".to_string()];
+    let crossword = crate::crossword::initialise(&words);
+    let mut grid = Grid::new(&crossword);
+    let initial_grid = grid.clone();
+    let cross_data = CrossData {
+        row: 1,
+        start_point: 0,
+        direction: Direction::Down,
+        order: 1,
+    };
+
+    grid.insert(1, "tall", &cross_data);
+    assert_eq!(Some('t'), grid.letter([1, 0]));
+    assert_eq!(Some('l'), grid.letter([1, 3]));
+    assert!(grid.has_word([1, 0], Direction::Down));
+    assert!(grid.has_word([1, 0], Direction::Across));
+    assert_eq!(2, grid.next_order());
+
+    grid.remove(1, "tall", &cross_data);
+    assert_eq!(Some('t'), grid.letter([1, 0]));
+    assert!(!grid.has_word([1, 0], Direction::Down));
+    assert_eq!(None, grid.letter([1, 3]));
+    assert_eq!(initial_grid, grid);
+}
+
+#[test]
+fn grid_is_crossable() {
+    let words = vec!["start".to_string(), "tall".to_string(), "as".to_string()];
+    let mut crossword = crate::crossword::initialise(&words);
+    crossword.words[1].cross = Some(CrossData {
+        row: 1,
+        start_point: 0,
+        direction: Direction::Down,
+        order: 1,
+    });
+    crossword.words[2].cross = Some(CrossData {
+        row: 2,
+        start_point: -2,
+        direction: Direction::Down,
+        order: 2,
+    });
+
+    let grid = Grid::new(&crossword);
+
+    assert!(!grid.is_crossable([1, 0], Direction::Down));
+    assert!(!grid.is_crossable([2, 0], Direction::Down));
+    assert!(grid.is_crossable([3, 0], Direction::Down));
+    assert!(grid.is_crossable([1, 2], Direction::Across));
+    assert!(!grid.is_crossable([1, 2], Direction::Down));
+}
+
+#[test]
+fn grid_key() {
+    let words = vec!["start".to_string(), "tall".to_string(), "lone".to_string()];
+    let tall = CrossData {
+        row: 1,
+        start_point: 0,
+        direction: Direction::Down,
+        order: 1,
+    };
+    let lone = CrossData {
+        row: 3,
+        start_point: 1,
+        direction: Direction::Across,
+        order: 2,
+    };
+    let crossword = crate::crossword::initialise(&words);
+    let mut grid = Grid::new(&crossword);
+    let initial_key = grid.key();
+
+    grid.insert(1, "tall", &tall);
+    grid.insert(2, "lone", &lone);
+    let key = grid.key();
+    assert_ne!(initial_key, key);
+
+    let mut crossword = crossword;
+    crossword.words[1].cross = Some(CrossData { order: 2, ..tall });
+    crossword.words[2].cross = Some(CrossData { order: 1, ..lone });
+    assert_eq!(key, Grid::new(&crossword).key());
+
+    let [x_shift, y_shift] = [-2, 4];
+    for word in &mut crossword.words {
+        if let Some(cross_data) = &mut word.cross {
+            let (row_shift, start_shift) = match cross_data.direction {
+                Direction::Across => (y_shift, x_shift),
+                Direction::Down => (x_shift, y_shift),
+            };
+            cross_data.row += row_shift;
+            cross_data.start_point += start_shift;
+        }
+    }
+    assert_eq!(key, Grid::new(&crossword).key());
+
+    crossword.words[2].cross = None;
+    assert_ne!(key, Grid::new(&crossword).key());
+    assert_ne!(initial_key, Grid::new(&crossword).key());
+
+    grid.remove(2, "lone", &lone);
+    assert_eq!(Grid::new(&crossword).key(), grid.key());
+}
@@ -0,0 +1,21 @@
+use crate::crossword::{CrossData, Direction, WordCross};
+
+/// A word placed at `row` and `start_point`, with no clue.
+pub(crate) fn helper_word(
+    word: &str,
+    row: i32,
+    start_point: i32,
+    direction: Direction,
+) -> WordCross<'_> {
+    WordCross {
+        word,
+        clue: None,
+        display: None,
+        cross: Some(CrossData {
+            row,
+            start_point,
+            direction,
+            order: 0,
+        }),
+    }
+}
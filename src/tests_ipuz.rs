@@ -0,0 +1,289 @@
+use crate::solver::Solver;
+use crate::tests_helpers::helper_word;
+
+use super::*;
+
+#[test]
+fn crossword_to_ipuz() {
+    let clue = Clue {
+        text: "Be".to_string(),
+        enumeration: Some("(5)".to_string()),
+    };
+    let mut crossword = Crossword {
+        words: vec![
+            helper_word("for", 0, 0, Direction::Across),
+            helper_word("of", 0, -1, Direction::Down),
+        ],
+    };
+    crossword.words[0].clue = Some(&clue);
+
+    let expected = r##"{
+  "version": "http://ipuz.org/v2",
+  "kind": [
+    "http://ipuz.org/crossword#1"
+  ],
+  "dimensions": {
+    "width": 3,
+    "height": 2
+  },
+  "block": "#",
+  "empty": 0,
+  "puzzle": [
+    [
+      1,
+      "#",
+      "#"
+    ],
+    [
+      2,
+      0,
+      0
+    ]
+  ],
+  "solution": [
+    [
+      "O",
+      "#",
+      "#"
+    ],
+    [
+      "F",
+      "O",
+      "R"
+    ]
+  ],
+  "clues": {
+    "Across": [
+      {
+        "number": 2,
+        "clue": "Be",
+        "enumeration": "(5)"
+      }
+    ],
+    "Down": [
+      [
+        1,
+        ""
+      ]
+    ]
+  }
+}
+"##;
+    assert_eq!(expected, to_ipuz(&crossword));
+}
+
+#[test]
+fn sample_words_round_trip() {
+    let solver = Solver::new(
+        &["hereby", "exist", "words", "for", "sample", "output"],
+        SolverOptions::default(),
+    );
+    let crosswords = solver.solve().crosswords;
+    assert!(!crosswords.is_empty());
+
+    for crossword in &crosswords {
+        let text = to_ipuz(crossword);
+        let loaded = from_ipuz(&text, &SolverOptions::default()).unwrap();
+        let loaded_crossword = loaded.crossword();
+
+        let mut words = loaded.words.clone();
+        words.sort();
+        let mut expected_words = solver.words().to_vec();
+        expected_words.sort();
+        assert_eq!(expected_words, words);
+        assert_eq!(
+            crossword.get_width_height(),
+            loaded_crossword.get_width_height()
+        );
+        assert_eq!(
+            crossword.count_crossings(),
+            loaded_crossword.count_crossings()
+        );
+        assert_eq!(text, to_ipuz(&loaded_crossword));
+    }
+}
+
+#[test]
+fn corner_round_trip() {
+    // The Across words only touch at a corner, so "bb" has to be placed between them.
+    let crossword = Crossword {
+        words: vec![
+            helper_word("ab", 0, 0, Direction::Across),
+            helper_word("bb", 1, 0, Direction::Down),
+            helper_word("bc", 1, 1, Direction::Across),
+        ],
+    };
+
+    let loaded = from_ipuz(&to_ipuz(&crossword), &SolverOptions::default()).unwrap();
+
+    assert_eq!(crossword.get_letters(), loaded.crossword().get_letters());
+}
+
+#[test]
+fn joined_rows_round_trip() {
+    let solver = Solver::new(
+        &[
+            "rts", "tsiiao", "stre", "osiis", "sri", "sieara", "ssaiaa", "aeiiit",
+        ],
+        SolverOptions::default(),
+    );
+    let crosswords = solver.solve().crosswords;
+    assert!(!crosswords.is_empty());
+
+    for crossword in &crosswords {
+        let text = to_ipuz(crossword);
+        let loaded = from_ipuz(&text, &SolverOptions::default()).unwrap();
+
+        assert_eq!(text, to_ipuz(&loaded.crossword()));
+    }
+}
+
+#[test]
+fn from_ipuz_joined_rows() {
+    // "jklm" and "rstu" lie in neighbouring rows, so "mr" has to be placed before the second.
+    let text = r##"{
+        "kind": ["http://ipuz.org/crossword#1"],
+        "solution": [
+            ["#", "B", "C", "D", "E", "F", "#"],
+            ["#", "G", "#", "#", "#", "N", "#"],
+            ["#", "H", "#", "#", "#", "O", "#"],
+            ["#", "I", "#", "#", "#", "P", "#"],
+            ["J", "K", "L", "M", "#", "Q", "#"],
+            ["#", "#", "#", "R", "S", "T", "U"]
+        ]
+    }"##;
+
+    let loaded = from_ipuz(text, &SolverOptions::default()).unwrap();
+
+    let mut words = loaded.words.clone();
+    words.sort();
+    assert_eq!(
+        vec!["bcdef", "bghik", "fnopqt", "jklm", "mr", "rstu"],
+        words
+    );
+    assert!(loaded.crossword().is_connected());
+}
+
+#[test]
+fn clues_round_trip() {
+    let clues = [
+        Clue {
+            text: "Preposition".to_string(),
+            enumeration: None,
+        },
+        Clue {
+            text: "Belonging to".to_string(),
+            enumeration: Some("(2)".to_string()),
+        },
+    ];
+    let mut crossword = Crossword {
+        words: vec![
+            helper_word("for", 0, 0, Direction::Across),
+            helper_word("of", 0, -1, Direction::Down),
+        ],
+    };
+    crossword.words[0].clue = Some(&clues[0]);
+    crossword.words[1].clue = Some(&clues[1]);
+
+    let loaded = from_ipuz(&to_ipuz(&crossword), &SolverOptions::default()).unwrap();
+
+    assert_eq!(vec!["for".to_string(), "of".to_string()], loaded.words);
+    assert_eq!(
+        vec![Some(clues[0].clone()), Some(clues[1].clone())],
+        loaded.clues
+    );
+}
+
+#[test]
+fn from_ipuz_other_conventions() {
+    let text = r#"{
+        "kind": ["http://ipuz.org/crossword#1"],
+        "block": null,
+        "solution": [
+            [null, {"value": "o"}],
+            ["f", "O", "R"]
+        ],
+        "clues": {
+            "Across": [{"number": "2", "clue": "Preposition"}, "garbage"],
+            "Down": [[1, ""]]
+        }
+    }"#;
+
+    let loaded = from_ipuz(text, &SolverOptions::default()).unwrap();
+
+    assert_eq!(vec!["for".to_string(), "oo".to_string()], loaded.words);
+    assert_eq!(
+        vec![
+            Some(Clue {
+                text: "Preposition".to_string(),
+                enumeration: None,
+            }),
+            None,
+        ],
+        loaded.clues
+    );
+}
+
+#[test]
+fn digraph_cells_round_trip() {
+    let mut options = SolverOptions::default();
+    options.normalisation.digraphs = vec!["ll".to_string()];
+    let solver = Solver::new(&["llan", "allt"], options);
+    let solution = solver.solve();
+    assert!(!solution.crosswords.is_empty());
+
+    for crossword in &solution.crosswords {
+        let text = to_ipuz(crossword);
+        let loaded = from_ipuz(&text, &SolverOptions::default()).unwrap();
+
+        assert!(text.contains("\"LL\""));
+        assert_eq!(solver.words(), loaded.words);
+        assert_eq!(text, to_ipuz(&loaded.crossword()));
+    }
+}
+
+#[test]
+fn from_ipuz_strip_diacritics() {
+    let text = r##"{
+        "kind": ["http://ipuz.org/crossword#1"],
+        "solution": [["É", "T", "É"], ["#", "#", "T"], ["#", "#", "É"]]
+    }"##;
+    let mut options = SolverOptions::default();
+    options.normalisation.strip_diacritics = true;
+
+    let loaded = from_ipuz(text, &options).unwrap();
+
+    assert_eq!(vec!["ete".to_string(), "ete".to_string()], loaded.words);
+}
+
+#[test]
+fn from_ipuz_errors() {
+    let options = SolverOptions::default();
+
+    assert!(matches!(
+        from_ipuz("{", &options),
+        Err(LoadError::Syntax(_))
+    ));
+    assert!(matches!(
+        from_ipuz(r#"{"kind": ["http://ipuz.org/sudoku#1"]}"#, &options),
+        Err(LoadError::Schema(_))
+    ));
+    assert!(matches!(
+        from_ipuz(r#"{"kind": ["http://ipuz.org/crossword#1"]}"#, &options),
+        Err(LoadError::Schema(_))
+    ));
+    assert!(matches!(
+        from_ipuz(
+            r##"{"kind": ["http://ipuz.org/crossword#1"], "solution": [["A", "B", "#", "C", "D"]]}"##,
+            &options
+        ),
+        Err(LoadError::Layout(_))
+    ));
+    assert!(matches!(
+        from_ipuz(
+            r#"{"kind": ["http://ipuz.org/crossword#1"], "solution": [["A", "XYZ"]]}"#,
+            &options
+        ),
+        Err(LoadError::Schema(_))
+    ));
+}
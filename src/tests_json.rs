@@ -0,0 +1,271 @@
+use super::*;
+
+#[test]
+fn parse_values() {
+    assert_eq!(Ok(Value::Null), parse("null"));
+    assert_eq!(Ok(Value::Bool(true)), parse(" true "));
+    assert_eq!(Ok(Value::Bool(false)), parse("false"));
+    assert_eq!(Ok(Value::Number(-12.5)), parse("-12.5"));
+    assert_eq!(Ok(Value::Number(300.0)), parse("3e2"));
+    assert_eq!(Ok(Value::from("café")), parse("\"café\""));
+    assert_eq!(
+        Ok(Value::Array(vec![Value::from(1_i64), Value::Null])),
+        parse("[1, null]")
+    );
+    assert_eq!(Ok(Value::Array(vec![])), parse("[ ]"));
+    assert_eq!(Ok(Value::Object(vec![])), parse("{}"));
+}
+
+#[test]
+fn parse_escapes() {
+    assert_eq!(
+        Ok(Value::from("a\"b\\c/d\ne\tf")),
+        parse(r#""a\"b\\c\/d\ne\tf""#)
+    );
+    assert_eq!(Ok(Value::from("é")), parse(r#""\u00e9""#));
+    assert_eq!(Ok(Value::from("😀")), parse(r#""\ud83d\ude00""#));
+    assert!(parse(r#""\ud83d""#).is_err());
+    assert!(parse(r#""\ud83d\u0041""#).is_err());
+    assert!(parse(r#""\x""#).is_err());
+}
+
+#[test]
+fn parse_object() {
+    let value = parse(r#"{"word": "hereby", "row": -3, "cross": null}"#).unwrap();
+
+    assert_eq!(Some("hereby"), value.get("word").unwrap().as_str());
+    assert_eq!(Some(-3), value.get("row").unwrap().as_i64());
+    assert!(value.get("cross").unwrap().is_null());
+    assert_eq!(None, value.get("missing"));
+    assert_eq!(None, Value::Null.get("word"));
+}
+
+#[test]
+fn parse_errors() {
+    assert!(parse("").is_err());
+    assert!(parse("[1, 2").is_err());
+    assert!(parse("{\"a\" 1}").is_err());
+    assert!(parse("\"unterminated").is_err());
+    assert!(parse("nul").is_err());
+    assert!(parse("1 2").is_err());
+    assert!(parse("--1").is_err());
+
+    let nested = format!("{}{}", "[".repeat(MAX_DEPTH), "]".repeat(MAX_DEPTH));
+    assert!(parse(&nested).is_ok());
+    let nested = format!(
+        "{}1{}",
+        "[{\"a\":".repeat(MAX_DEPTH),
+        "}]".repeat(MAX_DEPTH)
+    );
+    assert!(parse(&nested).is_err());
+    assert!(parse(&"[".repeat(1_000_000)).is_err());
+}
+
+#[test]
+fn value_accessors() {
+    assert_eq!(Some(2.5), Value::Number(2.5).as_f64());
+    assert_eq!(None, Value::Number(2.5).as_i64());
+    assert_eq!(None, Value::Number(1e300).as_i64());
+    assert_eq!(None, Value::Number(f64::INFINITY).as_i64());
+    assert_eq!(None, Value::Number(9223372036854775808.0).as_i64());
+    assert_eq!(
+        Some(i64::MIN),
+        Value::Number(-9223372036854775808.0).as_i64()
+    );
+    assert_eq!(None, Value::Null.as_str());
+    assert_eq!(Some(&[][..]), Value::Array(vec![]).as_array());
+    assert_eq!(None, Value::Null.as_array());
+}
+
+#[test]
+fn value_to_pretty_string() {
+    let value = Value::Object(vec![
+        ("word".to_string(), Value::from("say \"hi\"\n")),
+        (
+            "cells".to_string(),
+            Value::Array(vec![Value::from(-1_i64), Value::Number(0.5)]),
+        ),
+        ("empty".to_string(), Value::Array(vec![])),
+        ("cross".to_string(), Value::Null),
+        ("valid".to_string(), Value::Bool(true)),
+    ]);
+
+    let text = value.to_pretty_string();
+
+    assert_eq!(
+        "{\n  \"word\": \"say \\\"hi\\\"\\n\",\n  \"cells\": [\n    -1,\n    0.5\n  ],\n  \"empty\": [],\n  \"cross\": null,\n  \"valid\": true\n}\n",
+        text
+    );
+    assert_eq!(Ok(value), parse(&text));
+}
+
+// Cases from the JSONTestSuite conformance set (https://github.com/nst/JSONTestSuite), named after
+// its files. Those starting "y_" must parse and those starting "n_" must not.
+const ACCEPTED: &[(&str, &str)] = &[
+    ("y_array_arraysWithSpaces", "[[]   ]"),
+    ("y_array_empty-string", "[\"\"]"),
+    ("y_array_empty", "[]"),
+    ("y_array_ending_with_newline", "[\"a\"]\n"),
+    ("y_array_false", "[false]"),
+    ("y_array_heterogeneous", "[null, 1, \"1\", {}]"),
+    ("y_array_null", "[null]"),
+    ("y_array_with_leading_space", " [1]"),
+    ("y_array_with_several_null", "[1,null,null,null,2]"),
+    ("y_array_with_trailing_space", "[2] "),
+    ("y_number", "[123e65]"),
+    ("y_number_0e+1", "[0e+1]"),
+    ("y_number_0e1", "[0e1]"),
+    ("y_number_after_space", "[ 4]"),
+    ("y_number_double_close_to_zero", "[-0.000000000000000000000000000000000000000000000000000000000000000000000000000001]"),
+    ("y_number_int_with_exp", "[20e1]"),
+    ("y_number_minus_zero", "[-0]"),
+    ("y_number_negative_int", "[-123]"),
+    ("y_number_negative_one", "[-1]"),
+    ("y_number_real_capital_e", "[1E22]"),
+    ("y_number_real_capital_e_neg_exp", "[1E-2]"),
+    ("y_number_real_capital_e_pos_exp", "[1E+2]"),
+    ("y_number_real_exponent", "[123e45]"),
+    ("y_number_real_fraction_exponent", "[123.456e78]"),
+    ("y_number_real_neg_exp", "[1e-2]"),
+    ("y_number_real_pos_exponent", "[1e+2]"),
+    ("y_number_simple_int", "[123]"),
+    ("y_number_simple_real", "[123.456789]"),
+    ("y_object", "{\"asd\":\"sdf\", \"dfg\":\"fgh\"}"),
+    ("y_object_basic", "{\"asd\":\"sdf\"}"),
+    ("y_object_duplicated_key", "{\"a\":\"b\",\"a\":\"c\"}"),
+    ("y_object_empty", "{}"),
+    ("y_object_empty_key", "{\"\":0}"),
+    ("y_object_escaped_null_in_key", "{\"foo\\u0000bar\": 42}"),
+    ("y_object_extreme_numbers", "{ \"min\": -1.0e+28, \"max\": 1.0e+28 }"),
+    ("y_object_long_strings", "{\"x\":[{\"id\": \"xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx\"}], \"id\": \"xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx\"}"),
+    ("y_object_simple", "{\"a\":[]}"),
+    ("y_object_string_unicode", "{\"title\":\"\\u041f\\u043e\\u043b\\u0442\\u043e\\u0440\\u0430\" }"),
+    ("y_object_with_newlines", "{\n\"a\": \"b\"\n}"),
+    ("y_string_1_2_3_bytes_UTF-8_sequences", "[\"\\u0060\\u012a\\u12AB\"]"),
+    ("y_string_accepted_surrogate_pair", "[\"\\uD801\\udc37\"]"),
+    ("y_string_allowed_escapes", "[\"\\\"\\\\\\/\\b\\f\\n\\r\\t\"]"),
+    ("y_string_backslash_and_u_escaped_zero", "[\"\\\\u0000\"]"),
+    ("y_string_comments", "[\"a/*b*/c/*d//e\"]"),
+    ("y_string_escaped_control_character", "[\"\\u0012\"]"),
+    ("y_string_in_array_with_leading_space", "[ \"asd\"]"),
+    ("y_string_nonCharacterInUTF-8_U+FFFF", "[\"\u{ffff}\"]"),
+    ("y_string_null_escape", "[\"\\u0000\"]"),
+    ("y_string_unescaped_char_delete", "[\"\u{7f}\"]"),
+    ("y_string_unicode_U+10FFFE_nonchar", "[\"\\uDBFF\\uDFFE\"]"),
+    ("y_string_utf8", "[\"€𝄞\"]"),
+    ("y_structure_lonely_false", "false"),
+    ("y_structure_lonely_int", "42"),
+    ("y_structure_lonely_negative_real", "-0.1"),
+    ("y_structure_lonely_null", "null"),
+    ("y_structure_lonely_string", "\"asd\""),
+    ("y_structure_string_empty", "\"\""),
+    ("y_structure_trailing_newline", "[\"a\"]\n"),
+    ("y_structure_whitespace_array", " [] "),
+];
+
+const REJECTED: &[(&str, &str)] = &[
+    ("n_array_1_true_without_comma", "[1 true]"),
+    ("n_array_colon_instead_of_comma", "[\"\": 1]"),
+    ("n_array_comma_after_close", "[\"\"],"),
+    ("n_array_comma_and_number", "[,1]"),
+    ("n_array_double_comma", "[1,,2]"),
+    ("n_array_extra_close", "[\"x\"]]"),
+    ("n_array_extra_comma", "[\"\",]"),
+    ("n_array_incomplete", "[\"x\""),
+    ("n_array_inner_array_no_comma", "[3[4]]"),
+    ("n_array_just_comma", "[,]"),
+    ("n_array_missing_value", "[   , \"\"]"),
+    ("n_array_number_and_comma", "[1,]"),
+    ("n_array_unclosed", "[\"\""),
+    ("n_incomplete_false", "[fals]"),
+    ("n_incomplete_null", "[nul]"),
+    ("n_incomplete_true", "[tru]"),
+    ("n_number_++", "[++1234]"),
+    ("n_number_+1", "[+1]"),
+    ("n_number_-01", "[-01]"),
+    ("n_number_-1.0.", "[-1.0.]"),
+    ("n_number_-NaN", "[-NaN]"),
+    ("n_number_.-1", "[.-1]"),
+    ("n_number_.2e-3", "[.2e-3]"),
+    ("n_number_0.e1", "[0.e1]"),
+    ("n_number_0_capital_E+", "[0E+]"),
+    ("n_number_0e", "[0e]"),
+    ("n_number_1.0e+", "[1.0e+]"),
+    ("n_number_1eE2", "[1eE2]"),
+    ("n_number_2.e3", "[2.e3]"),
+    ("n_number_9.e+", "[9.e+]"),
+    ("n_number_Inf", "[Inf]"),
+    ("n_number_NaN", "[NaN]"),
+    ("n_number_hex_1_digit", "[0x1]"),
+    ("n_number_infinity", "[Infinity]"),
+    ("n_number_minus_infinity", "[-Infinity]"),
+    ("n_number_minus_sign_with_trailing_garbage", "[-foo]"),
+    ("n_number_minus_space_1", "[- 1]"),
+    ("n_number_neg_int_starting_with_zero", "[-012]"),
+    ("n_number_neg_real_without_int_part", "[-.123]"),
+    ("n_number_real_without_fractional_part", "[1.]"),
+    ("n_number_starting_with_dot", "[.123]"),
+    ("n_number_with_leading_zero", "[012]"),
+    ("n_object_bad_value", "[\"x\", truth]"),
+    ("n_object_comma_instead_of_colon", "{\"x\", null}"),
+    ("n_object_double_colon", "{\"x\"::\"b\"}"),
+    ("n_object_missing_colon", "{\"a\" b}"),
+    ("n_object_missing_key", "{:\"b\"}"),
+    ("n_object_missing_semicolon", "{\"a\" \"b\"}"),
+    ("n_object_missing_value", "{\"a\":"),
+    ("n_object_no-colon", "{\"a\""),
+    ("n_object_non_string_key", "{1:1}"),
+    ("n_object_single_quote", "{'a':0}"),
+    ("n_object_trailing_comma", "{\"id\":0,}"),
+    ("n_object_unquoted_key", "{a: \"b\"}"),
+    ("n_object_with_trailing_garbage", "{\"a\": true} \"x\""),
+    ("n_single_space", " "),
+    ("n_string_1_surrogate_then_escape", "[\"\\uD800\\\"]"),
+    ("n_string_escape_x", "[\"\\x00\"]"),
+    ("n_string_escaped_emoji", "[\"\\🌀\"]"),
+    ("n_string_incomplete_escape", "[\"\\\"]"),
+    (
+        "n_string_incomplete_surrogate_escape_invalid",
+        "[\"\\uD800\\uD800\\x\"]",
+    ),
+    ("n_string_invalid_unicode_escape", "[\"\\uqqqq\"]"),
+    ("n_string_leading_uescaped_thinspace", "[\\u0020\"asd\"]"),
+    ("n_string_no_quotes_with_bad_escape", "[\\n]"),
+    ("n_string_single_quote", "['single quote']"),
+    ("n_string_unescaped_ctrl_char", "[\"a\u{0}a\"]"),
+    ("n_string_unescaped_newline", "[\"new\nline\"]"),
+    ("n_string_unescaped_tab", "[\"\t\"]"),
+    ("n_structure_U+2060_word_joined", "[\u{2060}]"),
+    ("n_structure_angle_bracket_null", "[<null>]"),
+    ("n_structure_array_with_unclosed_string", "[\"asd]"),
+    ("n_structure_capitalized_True", "[True]"),
+    ("n_structure_close_unopened_array", "1]"),
+    ("n_structure_no_data", ""),
+    ("n_structure_null-byte-outside-string", "[\u{0}]"),
+    (
+        "n_structure_object_with_comment",
+        "{\"a\":/*comment*/\"b\"}",
+    ),
+    ("n_structure_single_star", "*"),
+    ("n_structure_trailing_#", "{\"a\":\"b\"}#{}"),
+    ("n_structure_unclosed_array", "[1"),
+    ("n_structure_unclosed_object", "{\"asd\":\"asd\""),
+    ("n_structure_whitespace_formfeed", "[\u{c}]"),
+];
+
+#[test]
+fn parse_conformance() {
+    for (name, text) in ACCEPTED {
+        assert!(parse(text).is_ok(), "{} was rejected", name);
+    }
+    for (name, text) in REJECTED {
+        assert!(parse(text).is_err(), "{} was accepted", name);
+    }
+}
+
+#[test]
+fn parse_number_out_of_range() {
+    assert!(parse("[1e400]").is_err());
+    assert!(parse("[-1e400]").is_err());
+    assert_eq!(Ok(Value::Number(0.0)), parse("1e-400"));
+}
@@ -43,6 +43,143 @@ fn letters_get_map() {
     helper_word_and_letter_vector_contains_word_index(&e_entry, 5);
 }
 
+#[test]
+fn letters_get_map_non_ascii() {
+    let words = vec!["straße".to_string(), "дом".to_string(), "λόγος".to_string()];
+
+    let letter_map = get_map(&words);
+
+    let sharp_s = &letter_map.get(&'ß').unwrap()[0];
+    assert_eq!(4, sharp_s.letter_index);
+    assert_eq!(1, sharp_s.n_letters_after);
+
+    let de = &letter_map.get(&'д').unwrap()[0];
+    assert_eq!(0, de.letter_index);
+    assert_eq!(2, de.n_letters_after);
+
+    let omicron_tonos = &letter_map.get(&'ό').unwrap()[0];
+    assert_eq!(1, omicron_tonos.letter_index);
+    assert_eq!(3, omicron_tonos.n_letters_after);
+    assert_eq!(1, letter_map.get(&'ο').unwrap().len());
+}
+
+#[test]
+fn letters_to_lowercase_non_ascii() {
+    assert_eq!(
+        vec!["straße".to_string(), "дом".to_string(), "λόγος".to_string()],
+        to_lowercase(&["STRAßE", "ДОМ", "ΛΌΓΟΣ"])
+    );
+}
+
+#[test]
+fn letters_normalise() {
+    let lowercase = Normalisation::default();
+    assert_eq!("café au lait", normalise("Café au LAIT", &lowercase));
+
+    let accents = Normalisation {
+        strip_diacritics: true,
+        ..Normalisation::default()
+    };
+    assert_eq!("cafe", normalise("Café", &accents));
+    assert_eq!("lodz", normalise("Łódź", &accents));
+    assert_eq!("straße", normalise("straße", &accents));
+    assert_eq!("λογος", normalise("λόγος", &accents));
+    assert_eq!("еж", normalise("ёж", &accents));
+    assert_eq!("cafe", normalise("cafe\u{301}", &accents));
+    assert_eq!("creme", normalise("cre\u{300}me", &accents));
+    assert_eq!("viet", normalise("việt", &accents));
+
+    let separators = Normalisation {
+        drop_separators: true,
+        ..Normalisation::default()
+    };
+    assert_eq!("icecream", normalise("ice cream", &separators));
+    assert_eq!("oclock", normalise("o'clock", &separators));
+    assert_eq!("jackinthebox", normalise("jack-in-the-box", &separators));
+}
+
+#[test]
+fn letters_normalise_combining_accents() {
+    let lowercase = Normalisation::default();
+    assert_eq!("café", normalise("Cafe\u{301}", &lowercase));
+    assert_eq!("việt", normalise("Vie\u{323}\u{302}t", &lowercase));
+    assert_eq!("ἄλφα", normalise("α\u{313}\u{301}λφα", &lowercase));
+    assert_eq!("ёж", normalise("е\u{308}ж", &lowercase));
+    // No letter has both, so the second accent stays on its own.
+    assert_eq!("ñ\u{301}", normalise("n\u{303}\u{301}", &lowercase));
+}
+
+#[test]
+fn letters_normalise_digraphs() {
+    let welsh = Normalisation {
+        digraphs: vec!["ll".to_string(), "ch".to_string()],
+        ..Normalisation::default()
+    };
+    let ll = get_digraph_cell("ll").unwrap();
+    let ch = get_digraph_cell("ch").unwrap();
+
+    let cells = normalise("Llanelli", &welsh);
+    assert_eq!(
+        vec![ll, 'a', 'n', 'e', ll, 'i'],
+        cells.chars().collect::<Vec<_>>()
+    );
+    assert_eq!("llanelli", to_display(&cells));
+    assert_eq!(
+        vec!['a', ch],
+        normalise("ach", &welsh).chars().collect::<Vec<_>>()
+    );
+
+    let hungarian = Normalisation {
+        digraphs: vec!["dz".to_string(), "dzs".to_string()],
+        ..Normalisation::default()
+    };
+    let dzs = get_digraph_cell("dzs").unwrap();
+    assert_eq!(
+        vec!['a', dzs, 'a'],
+        normalise("adzsa", &hungarian).chars().collect::<Vec<_>>()
+    );
+
+    let unknown = Normalisation {
+        digraphs: vec!["xy".to_string()],
+        ..Normalisation::default()
+    };
+    assert_eq!("xyz", normalise("xyz", &unknown));
+}
+
+#[test]
+fn letters_digraph_cells() {
+    for digraph in DIGRAPHS {
+        let cell = get_digraph_cell(digraph).unwrap();
+        assert_eq!(*digraph, get_cell_text(cell));
+    }
+    assert_eq!(None, get_digraph_cell("xy"));
+    assert_eq!("é", get_cell_text('é'));
+    assert_eq!("\u{E0FF}", get_cell_text('\u{E0FF}'));
+}
+
+#[test]
+fn letters_get_map_normalised() {
+    let normalisation = Normalisation {
+        strip_diacritics: true,
+        digraphs: vec!["ll".to_string()],
+        drop_separators: true,
+    };
+    let words: Vec<String> = ["Pwll", "élan vital"]
+        .iter()
+        .map(|word| normalise(word, &normalisation))
+        .collect();
+
+    let letter_map = get_map(&words);
+
+    let ll = &letter_map.get(&get_digraph_cell("ll").unwrap()).unwrap()[0];
+    assert_eq!(2, ll.letter_index);
+    assert_eq!(0, ll.n_letters_after);
+    assert!(!letter_map.contains_key(&'é'));
+    assert!(!letter_map.contains_key(&' '));
+    assert_eq!(2, letter_map.get(&'l').unwrap().len());
+    assert_eq!(8, letter_map.get(&'e').unwrap()[0].n_letters_after);
+}
+
 fn helper_word_and_letter_vector_contains_word_index(
     word_and_letters: &Vec<WordAndLetter>,
     word_index: usize,
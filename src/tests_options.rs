@@ -1,5 +1,10 @@
+use std::sync::Arc;
+
 use crate::crossword::{self, WordCross};
 use crate::letters;
+use crate::pareto;
+use crate::scorer::{BoundingBox, Density};
+use crate::solver::{Heuristic, MaxSize};
 
 use super::*;
 
@@ -19,6 +24,8 @@ fn test_options_compare() {
         words: vec![
             WordCross {
                 word: "small",
+                clue: None,
+                display: None,
                 cross: Some(CrossData {
                     row: 0,
                     start_point: 0,
@@ -28,6 +35,8 @@ fn test_options_compare() {
             },
             WordCross {
                 word: "collection",
+                clue: None,
+                display: None,
                 cross: Some(CrossData {
                     row: -3,
                     start_point: -1,
@@ -37,6 +46,8 @@ fn test_options_compare() {
             },
             WordCross {
                 word: "of",
+                clue: None,
+                display: None,
                 cross: Some(CrossData {
                     row: 7,
                     start_point: -3,
@@ -46,6 +57,8 @@ fn test_options_compare() {
             },
             WordCross {
                 word: "words",
+                clue: None,
+                display: None,
                 cross: Some(CrossData {
                     row: 0,
                     start_point: -4,
@@ -56,17 +69,559 @@ fn test_options_compare() {
         ],
     };
 
-    options_compare(&letter_map, &mut crossword, &mut best_crosswords);
+    let options = SolverOptions::default();
+    let mut grid = Grid::new(&crossword);
+    options_compare(
+        &Search::new(&letter_map, &options),
+        &mut crossword,
+        &mut grid,
+        &mut Transpositions::default(),
+        &mut best_crosswords,
+    );
 
     assert_eq!(vec![expected_crossword], best_crosswords);
 }
 
+#[test]
+fn test_options_compare_threaded() {
+    let words = vec![
+        "hereby".to_string(),
+        "exist".to_string(),
+        "words".to_string(),
+        "for".to_string(),
+        "sample".to_string(),
+        "output".to_string(),
+    ];
+    let letter_map = letters::get_map(&words);
+    let options = SolverOptions::default();
+
+    let mut crossword = crossword::initialise(&words);
+    let mut best_crosswords = vec![];
+    let mut grid = Grid::new(&crossword);
+    options_compare(
+        &Search::new(&letter_map, &options),
+        &mut crossword,
+        &mut grid,
+        &mut Transpositions::default(),
+        &mut best_crosswords,
+    );
+    let best_crosswords = merge_crosswords(best_crosswords, &options);
+    assert!(!best_crosswords.is_empty());
+
+    for threads in [1, 2, 3, 8] {
+        let mut crossword = crossword::initialise(&words);
+        let mut grid = Grid::new(&crossword);
+        let threaded_crosswords = options_compare_threaded(
+            &Search::new(&letter_map, &options),
+            &mut crossword,
+            &mut grid,
+            threads,
+        );
+        assert_eq!(best_crosswords, threaded_crosswords);
+        assert_eq!(crossword::initialise(&words), crossword);
+        assert_eq!(Grid::new(&crossword), grid);
+    }
+}
+
+#[test]
+fn options_compare_size_bound_pruning() {
+    // Bounds a partial crossword by its own size, without the words left to place.
+    #[derive(Debug)]
+    struct PartialBoundingBox;
+    impl Scorer for PartialBoundingBox {
+        fn score(&self, crossword: &Crossword) -> f64 {
+            BoundingBox.score(crossword)
+        }
+    }
+
+    // Whether knowing the words left cuts any branches. Where the longest word is placed early,
+    // as in the first list, it cannot.
+    for (words, fewer_nodes) in [
+        (vec!["small", "collection", "of", "words"], false),
+        (
+            vec![
+                "for",
+                "exist",
+                "words",
+                "sample",
+                "output",
+                "hereby",
+                "extraordinary",
+            ],
+            true,
+        ),
+    ] {
+        let words: Vec<String> = words.into_iter().map(String::from).collect();
+        let letter_map = letters::get_map(&words);
+        let mut results = vec![];
+
+        for scorer in [
+            Arc::new(PartialBoundingBox) as Arc<dyn Scorer>,
+            Arc::new(BoundingBox),
+        ] {
+            let options = SolverOptions {
+                scorer,
+                ..SolverOptions::default()
+            };
+            let search = Search::new(&letter_map, &options);
+            let mut crossword = crossword::initialise(&words);
+            let mut grid = Grid::new(&crossword);
+            let mut best_crosswords = vec![];
+            options_compare(
+                &search,
+                &mut crossword,
+                &mut grid,
+                &mut Transpositions::default(),
+                &mut best_crosswords,
+            );
+
+            results.push((
+                merge_crosswords(best_crosswords, &options),
+                search.counters.statistics().nodes,
+            ));
+        }
+
+        let (partial_crosswords, partial_nodes) = &results[0];
+        let (crosswords, nodes) = &results[1];
+        assert!(!crosswords.is_empty());
+        assert_eq!(partial_crosswords, crosswords);
+        assert_eq!(fewer_nodes, nodes < partial_nodes);
+        assert!(nodes <= partial_nodes);
+    }
+}
+
+#[test]
+fn options_compare_skip_uncrossable() {
+    let words: Vec<String> = ["hereby", "exist", "words", "for", "sample", "output"]
+        .iter()
+        .map(|word| word.to_string())
+        .collect();
+    let letter_map = letters::get_map(&words);
+    let options = SolverOptions::default();
+    let mut results = vec![];
+
+    for skip_uncrossable in [false, true] {
+        let mut search = Search::new(&letter_map, &options);
+        search.skip_uncrossable = skip_uncrossable;
+        let mut crossword = crossword::initialise(&words);
+        let mut grid = Grid::new(&crossword);
+        let mut best_crosswords = vec![];
+        options_compare(
+            &search,
+            &mut crossword,
+            &mut grid,
+            &mut Transpositions::default(),
+            &mut best_crosswords,
+        );
+
+        results.push((
+            merge_crosswords(best_crosswords, &options),
+            search.counters.statistics(),
+        ));
+    }
+
+    // Only insertions which would have been rejected are left out, so the nodes stay the same.
+    let (all_crosswords, all_statistics) = &results[0];
+    let (crosswords, statistics) = &results[1];
+    assert_eq!(all_crosswords, crosswords);
+    assert_eq!(all_statistics.nodes, statistics.nodes);
+    assert!(statistics.rejected < all_statistics.rejected);
+}
+
+#[test]
+fn options_compare_best_score() {
+    let words = ["for".to_string(), "of".to_string()];
+    let letter_map = letters::get_map(&words);
+    let options = SolverOptions {
+        scorer: Arc::new(Density),
+        ..SolverOptions::default()
+    };
+    let search = Search::new(&letter_map, &options);
+    let mut crossword = crossword::initialise(&words);
+    assert!(place_word(
+        1,
+        0,
+        -1,
+        Direction::Down,
+        &options,
+        &mut crossword
+    ));
+    let score = score_key(Density.score(&crossword));
+
+    // Another thread found this score, and cleared the crosswords this one held.
+    search.best_score.store(score, Ordering::Relaxed);
+    assert_eq!(Comparison::First, search.compare(&crossword, &mut vec![]));
+
+    search.best_score.store(score - 1, Ordering::Relaxed);
+    assert_eq!(Comparison::Worse, search.compare(&crossword, &mut vec![]));
+}
+
+#[test]
+fn test_merge_crosswords() {
+    let crossword_small = Crossword {
+        words: vec![
+            WordCross {
+                word: "of",
+                clue: None,
+                display: None,
+                cross: Some(CrossData {
+                    row: 0,
+                    start_point: 0,
+                    direction: Direction::Across,
+                    order: 0,
+                }),
+            },
+            WordCross {
+                word: "for",
+                clue: None,
+                display: None,
+                cross: Some(CrossData {
+                    row: 0,
+                    start_point: -1,
+                    direction: Direction::Down,
+                    order: 1,
+                }),
+            },
+        ],
+    };
+    let mut crossword_small_mirror = crossword_small.clone();
+    crossword_small_mirror.words[1].cross = Some(CrossData {
+        row: 1,
+        start_point: -2,
+        direction: Direction::Down,
+        order: 1,
+    });
+    let mut crossword_large = crossword_small.clone();
+    crossword_large.words[1].cross = Some(CrossData {
+        row: 0,
+        start_point: -5,
+        direction: Direction::Down,
+        order: 1,
+    });
+
+    let mut crossword_small_transposed = crossword_small.clone();
+    for word in &mut crossword_small_transposed.words {
+        let cross_data = word.cross.as_mut().unwrap();
+        cross_data.direction = cross_data.direction.change();
+    }
+
+    let merged = merge_crosswords(
+        vec![
+            crossword_small_mirror.clone(),
+            crossword_large.clone(),
+            crossword_small_transposed,
+            crossword_small.clone(),
+            crossword_small_mirror.clone(),
+        ],
+        &SolverOptions::default(),
+    );
+
+    assert_eq!(
+        vec![crossword_small.clone(), crossword_small_mirror.clone()],
+        merged
+    );
+
+    let mut crossword_flat = crossword_small.clone();
+    crossword_flat.words[1].cross = Some(CrossData {
+        row: 0,
+        start_point: 0,
+        direction: Direction::Across,
+        order: 1,
+    });
+    let options = SolverOptions {
+        selection: Selection::ParetoFront { crossings: false },
+        ..SolverOptions::default()
+    };
+
+    let merged = merge_crosswords(
+        vec![
+            crossword_flat.clone(),
+            crossword_large.clone(),
+            crossword_small_mirror.clone(),
+            crossword_small.clone(),
+        ],
+        &options,
+    );
+
+    assert_eq!(
+        vec![crossword_small, crossword_small_mirror, crossword_flat],
+        merged
+    );
+}
+
+#[test]
+fn options_compare_pareto_front() {
+    let words = vec![
+        "planet".to_string(),
+        "orange".to_string(),
+        "violet".to_string(),
+        "marble".to_string(),
+        "silver".to_string(),
+        "copper".to_string(),
+    ];
+    let letter_map = letters::get_map(&words);
+
+    for crossings in [false, true] {
+        let options = SolverOptions {
+            selection: Selection::ParetoFront { crossings },
+            ..SolverOptions::default()
+        };
+        let mut crossword = crossword::initialise(&words);
+        let mut best_crosswords = vec![];
+        let mut grid = Grid::new(&crossword);
+        options_compare(
+            &Search::new(&letter_map, &options),
+            &mut crossword,
+            &mut grid,
+            &mut Transpositions::default(),
+            &mut best_crosswords,
+        );
+
+        assert!(best_crosswords.len() > 1);
+        for crossword in &best_crosswords {
+            let objectives = Objectives::new(crossword, crossings);
+            assert!(!pareto::is_dominated(
+                &objectives,
+                &best_crosswords,
+                crossings
+            ));
+        }
+        assert!(best_crosswords
+            .iter()
+            .any(|crossword| crossword.get_width_height() == (9, 8)));
+        assert!(best_crosswords
+            .iter()
+            .any(|crossword| crossword.get_width_height() == (6, 13)));
+    }
+}
+
+#[test]
+fn options_compare_block_overlaps() {
+    let words = vec![
+        "ab".to_string(),
+        "cd".to_string(),
+        "ac".to_string(),
+        "bd".to_string(),
+    ];
+    let letter_map = letters::get_map(&words);
+
+    let mut crossword = crossword::initialise(&words);
+    let mut best_crosswords = vec![];
+    let options = SolverOptions::default();
+    let mut grid = Grid::new(&crossword);
+    options_compare(
+        &Search::new(&letter_map, &options),
+        &mut crossword,
+        &mut grid,
+        &mut Transpositions::default(),
+        &mut best_crosswords,
+    );
+    assert!(best_crosswords.is_empty());
+
+    let options = SolverOptions {
+        block_overlaps: true,
+        ..SolverOptions::default()
+    };
+    let mut crossword = crossword::initialise(&words);
+    let mut best_crosswords = vec![];
+    let mut grid = Grid::new(&crossword);
+    options_compare(
+        &Search::new(&letter_map, &options),
+        &mut crossword,
+        &mut grid,
+        &mut Transpositions::default(),
+        &mut best_crosswords,
+    );
+    assert_eq!(1, best_crosswords.len());
+    assert_eq!((2, 2), best_crosswords[0].get_min_max());
+    assert!(best_crosswords[0].is_valid());
+}
+
+#[test]
+fn insert_word_block_overlaps_readme_example() {
+    let words = vec![
+        "relate".to_string(),
+        "tree".to_string(),
+        "truth".to_string(),
+        "remember".to_string(),
+    ];
+    let mut crossword = crossword::initialise(&words);
+    let strict_options = SolverOptions::default();
+    let mut grid = Grid::new(&crossword);
+    let block_options = SolverOptions {
+        block_overlaps: true,
+        ..SolverOptions::default()
+    };
+
+    let tree = WordAndLetter {
+        word_index: 1,
+        word: "tree",
+        letter: 'e',
+        letter_index: 2,
+        n_letters_after: 1,
+    };
+    assert!(insert_word(
+        5,
+        0,
+        Direction::Down,
+        &tree,
+        &block_options,
+        &mut crossword,
+        &mut grid
+    ));
+
+    let truth = WordAndLetter {
+        word_index: 2,
+        word: "truth",
+        letter: 't',
+        letter_index: 0,
+        n_letters_after: 4,
+    };
+    assert!(!insert_word(
+        4,
+        0,
+        Direction::Down,
+        &truth,
+        &strict_options,
+        &mut crossword,
+        &mut grid
+    ));
+    assert!(insert_word(
+        4,
+        0,
+        Direction::Down,
+        &truth,
+        &block_options,
+        &mut crossword,
+        &mut grid
+    ));
+    assert!(!crossword.is_valid());
+
+    let remember = WordAndLetter {
+        word_index: 3,
+        word: "remember",
+        letter: 'r',
+        letter_index: 0,
+        n_letters_after: 7,
+    };
+    assert!(!insert_word(
+        1,
+        4,
+        Direction::Across,
+        &remember,
+        &strict_options,
+        &mut crossword,
+        &mut grid
+    ));
+    assert!(insert_word(
+        1,
+        4,
+        Direction::Across,
+        &remember,
+        &block_options,
+        &mut crossword,
+        &mut grid
+    ));
+    assert!(crossword.all_words_crossed());
+    assert!(crossword.is_valid());
+}
+
+#[test]
+fn test_check_overlappable() {
+    let crossword = Crossword {
+        words: vec![WordCross {
+            word: "start",
+            clue: None,
+            display: None,
+            cross: Some(CrossData {
+                row: 0,
+                start_point: 0,
+                direction: Direction::Across,
+                order: 0,
+            }),
+        }],
+    };
+
+    let word = WordAndLetter {
+        word_index: 1,
+        word: "tan",
+        letter: 't',
+        letter_index: 0,
+        n_letters_after: 2,
+    };
+    assert!(check_overlappable(
+        1,
+        1,
+        Direction::Across,
+        &word,
+        &Grid::new(&crossword)
+    ));
+    assert!(check_overlappable(
+        1,
+        0,
+        Direction::Down,
+        &word,
+        &Grid::new(&crossword)
+    ));
+    assert!(check_overlappable(
+        4,
+        0,
+        Direction::Down,
+        &word,
+        &Grid::new(&crossword)
+    ));
+    assert!(!check_overlappable(
+        0,
+        5,
+        Direction::Across,
+        &word,
+        &Grid::new(&crossword)
+    ));
+    assert!(!check_overlappable(
+        0,
+        2,
+        Direction::Across,
+        &word,
+        &Grid::new(&crossword)
+    ));
+    assert!(!check_overlappable(
+        2,
+        0,
+        Direction::Down,
+        &word,
+        &Grid::new(&crossword)
+    ));
+    assert!(!check_overlappable(
+        5,
+        0,
+        Direction::Down,
+        &word,
+        &Grid::new(&crossword)
+    ));
+    assert!(!check_overlappable(
+        1,
+        1,
+        Direction::Down,
+        &word,
+        &Grid::new(&crossword)
+    ));
+    assert!(!check_overlappable(
+        1,
+        -3,
+        Direction::Down,
+        &word,
+        &Grid::new(&crossword)
+    ));
+}
+
 #[test]
 fn insert_word_check_insertable() {
     let mut crossword = Crossword {
         words: vec![
             WordCross {
                 word: "start",
+                clue: None,
+                display: None,
                 cross: Some(CrossData {
                     row: -3,
                     start_point: -4,
@@ -76,6 +631,8 @@ fn insert_word_check_insertable() {
             },
             WordCross {
                 word: "words",
+                clue: None,
+                display: None,
                 cross: Some(CrossData {
                     row: -1,
                     start_point: -5,
@@ -85,15 +642,21 @@ fn insert_word_check_insertable() {
             },
             WordCross {
                 word: "new",
+                clue: None,
+                display: None,
                 cross: None,
             },
             WordCross {
                 word: "unaddable",
+                clue: None,
+                display: None,
                 cross: None,
             },
         ],
     };
     let crossword_initial = crossword.clone();
+    let options = SolverOptions::default();
+    let mut grid = Grid::new(&crossword);
 
     let word = WordAndLetter {
         word_index: 1,
@@ -102,7 +665,15 @@ fn insert_word_check_insertable() {
         letter_index: 4,
         n_letters_after: 0,
     };
-    assert!(!insert_word(-4, -3, Direction::Down, &word, &mut crossword));
+    assert!(!insert_word(
+        -4,
+        -3,
+        Direction::Down,
+        &word,
+        &options,
+        &mut crossword,
+        &mut grid
+    ));
     assert_eq!(crossword_initial, crossword);
 
     let word = WordAndLetter {
@@ -117,14 +688,16 @@ fn insert_word_check_insertable() {
         -4,
         Direction::Across,
         &word,
-        &crossword
+        &Grid::new(&crossword)
     ));
     assert!(!insert_word(
         -2,
         -1,
         Direction::Across,
         &word,
-        &mut crossword
+        &options,
+        &mut crossword,
+        &mut grid
     ));
     assert_eq!(crossword_initial, crossword);
 
@@ -140,9 +713,17 @@ fn insert_word_check_insertable() {
         -8,
         Direction::Down,
         &word,
-        &crossword
+        &Grid::new(&crossword)
+    ));
+    assert!(!insert_word(
+        -2,
+        -3,
+        Direction::Down,
+        &word,
+        &options,
+        &mut crossword,
+        &mut grid
     ));
-    assert!(!insert_word(-2, -3, Direction::Down, &word, &mut crossword));
     assert_eq!(crossword_initial, crossword);
 
     let word = WordAndLetter {
@@ -157,14 +738,16 @@ fn insert_word_check_insertable() {
         -3,
         Direction::Across,
         &word,
-        &crossword
+        &Grid::new(&crossword)
     ));
     assert!(insert_word(
         -5,
         -1,
         Direction::Across,
         &word,
-        &mut crossword
+        &options,
+        &mut crossword,
+        &mut grid
     ));
     assert!(crossword != crossword_initial);
     assert!(crossword.words[2].cross != None);
@@ -180,290 +763,245 @@ fn insert_word_check_insertable() {
 }
 
 #[test]
-fn test_get_end_point() {
-    assert_eq!(22, get_end_point(18, "hello"));
-}
-
-#[test]
-fn test_check_same_direction() {
-    let crossword = Crossword {
-        words: vec![
-            WordCross {
-                word: "blue",
-                cross: Some(CrossData {
-                    row: 5,
-                    start_point: 3,
-                    direction: Direction::Down,
-                    order: 0,
-                }),
-            },
-            WordCross {
-                word: "skies",
-                cross: Some(CrossData {
-                    row: 6,
-                    start_point: 2,
-                    direction: Direction::Across,
-                    order: 1,
-                }),
-            },
-            WordCross {
-                word: "sailing",
-                cross: None,
-            },
-        ],
+fn insert_word_max_size() {
+    let words = vec!["start".to_string(), "tall".to_string()];
+    let mut crossword = crossword::initialise(&words);
+    let mut grid = Grid::new(&crossword);
+    let crossword_initial = crossword.clone();
+    let word = WordAndLetter {
+        word_index: 1,
+        word: "tall",
+        letter: 't',
+        letter_index: 0,
+        n_letters_after: 3,
+    };
+    let mut options = SolverOptions {
+        max_size: Some(MaxSize {
+            width: 5,
+            height: 3,
+            rotatable: false,
+        }),
+        ..SolverOptions::default()
     };
 
-    assert!(check_same_direction(
-        3,
-        14,
-        159,
-        3,
-        14,
+    assert!(!insert_word(
         1,
-        Direction::Across,
-        &crossword
-    ));
-    assert!(check_same_direction(
-        3,
-        14,
-        159,
-        3,
-        14,
-        159265,
-        Direction::Down,
-        &crossword
-    ));
-    assert!(check_same_direction(
-        3,
-        14,
-        159,
-        -8,
-        2,
-        158,
-        Direction::Down,
-        &crossword
-    ));
-    assert!(check_same_direction(
-        3,
-        14,
-        159,
-        -8,
-        2,
-        160,
-        Direction::Across,
-        &crossword
-    ));
-    assert!(check_same_direction(
-        3,
-        14,
-        159,
-        15,
-        16,
-        158,
-        Direction::Across,
-        &crossword
-    ));
-    assert!(check_same_direction(
-        3,
-        14,
-        159,
-        15,
-        16,
-        160,
+        0,
         Direction::Down,
-        &crossword
+        &word,
+        &options,
+        &mut crossword,
+        &mut grid
     ));
-    assert!(!check_same_direction(
-        3,
-        14,
-        159,
+    assert_eq!(crossword_initial, crossword);
+
+    options.max_size = Some(MaxSize {
+        width: 3,
+        height: 5,
+        rotatable: true,
+    });
+    assert!(!insert_word(
+        1,
         0,
-        4,
-        158,
-        Direction::Down,
-        &crossword
-    ));
-    assert!(!check_same_direction(
-        3,
-        14,
-        159,
-        13,
-        20,
-        160,
-        Direction::Across,
-        &crossword
-    ));
-    assert!(check_same_direction(
-        6,
-        12,
-        6,
-        3,
-        6,
-        5,
         Direction::Down,
-        &crossword
+        &word,
+        &options,
+        &mut crossword,
+        &mut grid
     ));
-    assert!(!check_same_direction(
-        -3,
-        3,
-        4,
-        3,
-        6,
-        5,
+
+    options.max_size = Some(MaxSize {
+        width: 4,
+        height: 5,
+        rotatable: true,
+    });
+    assert!(insert_word(
+        1,
+        0,
         Direction::Down,
-        &crossword
+        &word,
+        &options,
+        &mut crossword,
+        &mut grid
     ));
-    assert!(check_same_direction(
-        3,
-        14,
-        159,
-        -20,
-        -4,
-        159,
+}
+
+#[test]
+fn place_word_out_of_range() {
+    let words = vec!["start".to_string()];
+    let mut crossword = crossword::initialise_empty(&words);
+    let options = SolverOptions::default();
+
+    assert!(!place_word(
+        0,
+        0,
+        i32::MAX - 4,
         Direction::Across,
-        &crossword
-    ));
-    assert!(check_same_direction(
-        3,
-        14,
-        159,
-        20,
-        40,
-        159,
-        Direction::Down,
-        &crossword
+        &options,
+        &mut crossword
     ));
-    assert!(!check_same_direction(
-        3,
-        14,
-        159,
+    assert!(!place_word(
+        0,
+        i32::MIN,
         0,
-        8,
-        159,
         Direction::Down,
-        &crossword
-    ));
-    assert!(!check_same_direction(
-        3,
-        14,
-        159,
-        8,
-        23,
-        159,
-        Direction::Across,
-        &crossword
+        &options,
+        &mut crossword
     ));
-    assert!(!check_same_direction(
-        3,
-        14,
-        159,
+    assert!(place_word(
+        0,
         0,
-        20,
-        159,
+        i32::MAX - 5,
         Direction::Across,
-        &crossword
-    ));
-    assert!(!check_same_direction(
-        3,
-        14,
-        159,
-        5,
-        8,
-        159,
-        Direction::Down,
-        &crossword
+        &options,
+        &mut crossword
     ));
 }
 
 #[test]
-fn test_check_connecting_word() {
-    let words = vec!["lonely".to_string()];
-    let crossword = crossword::initialise(&words);
+fn test_place_word() {
+    let words = vec!["start".to_string(), "tall".to_string()];
+    let mut crossword = crossword::initialise_empty(&words);
+    let options = SolverOptions::default();
 
-    assert!(check_connecting_word(
-        3,
+    assert!(place_word(
+        1,
         4,
         0,
-        Direction::Across,
-        &crossword
+        Direction::Down,
+        &options,
+        &mut crossword
     ));
-    assert!(check_connecting_word(
-        3,
-        2,
+    assert!(!place_word(
+        0,
+        1,
         0,
         Direction::Across,
-        &crossword
+        &options,
+        &mut crossword
     ));
-    assert!(!check_connecting_word(3, 4, 0, Direction::Down, &crossword));
-    assert!(!check_connecting_word(
-        3,
-        4,
-        1,
+    assert!(place_word(
+        0,
+        0,
+        0,
         Direction::Across,
-        &crossword
+        &options,
+        &mut crossword
     ));
-    assert!(!check_connecting_word(
-        -1,
+    assert!(!place_word(
+        1,
         0,
         0,
         Direction::Across,
-        &crossword
+        &options,
+        &mut crossword
     ));
+    assert_eq!(
+        Some(CrossData {
+            row: 4,
+            start_point: 0,
+            direction: Direction::Down,
+            order: 0,
+        }),
+        crossword.words[1].cross
+    );
+    assert_eq!(
+        Some(CrossData {
+            row: 0,
+            start_point: 0,
+            direction: Direction::Across,
+            order: 1,
+        }),
+        crossword.words[0].cross
+    );
 }
 
 #[test]
-fn test_check_different_direction() {
-    assert!(check_different_direction(
-        0,
-        8,
-        3,
-        "different",
-        -16,
-        -13,
-        10,
-        "rows"
-    ));
-    assert!(!check_different_direction(
-        0, 4, 3, "words", 2, 6, 5, "touch"
-    ));
-    assert!(!check_different_direction(
-        9,
-        16,
-        -4,
-        "clashing",
-        -7,
-        4,
-        12,
-        "intersection"
+fn check_insertable_same_direction() {
+    let a = |start: i32, end: i32| "a".repeat((end - start + 1) as usize);
+    let check = |new: (i32, i32, i32), old: (i32, i32, i32), direction: Direction| {
+        let (new_start, new_end, new_row) = new;
+        let (old_start, old_end, old_row) = old;
+        helper_check_insertable(
+            &[
+                ("aaaa", 5, 3, Direction::Down),
+                ("aaaaa", 6, 2, Direction::Across),
+                (&a(old_start, old_end), old_row, old_start, direction),
+            ],
+            (&a(new_start, new_end), new_row, new_start, direction),
+        )
+    };
+
+    assert!(check((3, 14, 159), (3, 14, 1), Direction::Across));
+    assert!(check((3, 14, 159), (3, 14, 159265), Direction::Down));
+    assert!(check((3, 14, 159), (-8, 2, 158), Direction::Down));
+    assert!(check((3, 14, 159), (-8, 2, 160), Direction::Across));
+    assert!(check((3, 14, 159), (15, 16, 158), Direction::Across));
+    assert!(check((3, 14, 159), (15, 16, 160), Direction::Down));
+    assert!(!check((3, 14, 159), (0, 4, 158), Direction::Down));
+    assert!(!check((3, 14, 159), (13, 20, 160), Direction::Across));
+    assert!(check((6, 12, 6), (3, 6, 5), Direction::Down));
+    assert!(!check((-3, 3, 4), (3, 6, 5), Direction::Down));
+    assert!(check((3, 14, 159), (-20, -4, 159), Direction::Across));
+    assert!(check((3, 14, 159), (20, 40, 159), Direction::Down));
+    assert!(!check((3, 14, 159), (0, 8, 159), Direction::Down));
+    assert!(!check((3, 14, 159), (8, 23, 159), Direction::Across));
+    assert!(!check((3, 14, 159), (0, 20, 159), Direction::Across));
+    assert!(!check((3, 14, 159), (5, 8, 159), Direction::Down));
+}
+
+#[test]
+fn check_insertable_connecting_word() {
+    // Words side by side in neighbouring rows may only share a cell where a word crosses both.
+    let lonely = ("lonely", 0, 0, Direction::Across);
+    assert!(helper_check_insertable(
+        &[lonely, ("aae", 3, -2, Direction::Down)],
+        ("laa", 4, 0, Direction::Down)
     ));
-    assert!(check_different_direction(
-        9,
-        18,
-        -4,
-        "acceptable",
-        -7,
-        4,
-        12,
-        "intersection"
+    assert!(helper_check_insertable(
+        &[lonely, ("aae", 3, -2, Direction::Down)],
+        ("naa", 2, 0, Direction::Down)
     ));
-    assert!(check_different_direction(
-        9,
-        18,
-        -4,
-        "acceptable",
-        -7,
-        4,
-        18,
-        "intersection"
+    assert!(!helper_check_insertable(
+        &[lonely, ("aaa", 3, -2, Direction::Across)],
+        ("aaa", 4, 0, Direction::Across)
+    ));
+    assert!(!helper_check_insertable(
+        &[lonely, ("aea", 3, -1, Direction::Down)],
+        ("aaa", 4, 1, Direction::Down)
+    ));
+    assert!(!helper_check_insertable(
+        &[lonely, ("aal", 0, -2, Direction::Down)],
+        ("aaa", -1, 0, Direction::Down)
+    ));
+
+    let eclair = ("éclair", 0, 0, Direction::Across);
+    assert!(helper_check_insertable(
+        &[eclair, ("aai", 4, -2, Direction::Down)],
+        ("raa", 5, 0, Direction::Down)
+    ));
+    assert!(!helper_check_insertable(
+        &[eclair, ("aar", 5, -2, Direction::Down)],
+        ("aaa", 6, 0, Direction::Down)
     ));
 }
 
 #[test]
-fn test_get_nth_letter() {
-    assert_eq!('e', get_nth_letter("environment", 0));
-    assert_eq!('o', get_nth_letter("environment", 5));
-    assert_eq!('e', get_nth_letter("environment", 8));
-    assert_eq!('t', get_nth_letter("environment", 10));
+fn check_insertable_different_direction() {
+    let check = |new: (&str, i32, i32), old: (&str, i32, i32)| {
+        let (new_word, new_row, new_start) = new;
+        let (old_word, old_row, old_start) = old;
+        helper_check_insertable(
+            &[(old_word, old_row, old_start, Direction::Down)],
+            (new_word, new_row, new_start, Direction::Across),
+        )
+    };
+
+    assert!(check(("different", 3, 0), ("rows", 10, -16)));
+    assert!(!check(("words", 3, 0), ("touch", 5, 2)));
+    assert!(!check(("clashing", -4, 9), ("intersection", 12, -7)));
+    assert!(check(("acceptable", -4, 9), ("intersection", 12, -7)));
+    assert!(check(("acceptable", -4, 9), ("intersection", 18, -7)));
 }
 
 #[test]
@@ -472,6 +1010,8 @@ fn test_compare_crosswords() {
         words: vec![
             WordCross {
                 word: "alpha",
+                clue: None,
+                display: None,
                 cross: Some(CrossData {
                     row: 0,
                     start_point: 5,
@@ -481,6 +1021,8 @@ fn test_compare_crosswords() {
             },
             WordCross {
                 word: "bravoo",
+                clue: None,
+                display: None,
                 cross: Some(CrossData {
                     row: 9,
                     start_point: -2,
@@ -489,11 +1031,15 @@ fn test_compare_crosswords() {
                 }),
             },
             WordCross {
-                word: "charlie",
+                word: "char",
+                clue: None,
+                display: None,
                 cross: None,
             },
             WordCross {
                 word: "dalta",
+                clue: None,
+                display: None,
                 cross: Some(CrossData {
                     row: 6,
                     start_point: -2,
@@ -507,6 +1053,8 @@ fn test_compare_crosswords() {
         words: vec![
             WordCross {
                 word: "alpha",
+                clue: None,
+                display: None,
                 cross: Some(CrossData {
                     row: 0,
                     start_point: 5,
@@ -516,6 +1064,8 @@ fn test_compare_crosswords() {
             },
             WordCross {
                 word: "bravoo",
+                clue: None,
+                display: None,
                 cross: Some(CrossData {
                     row: 9,
                     start_point: -2,
@@ -524,11 +1074,15 @@ fn test_compare_crosswords() {
                 }),
             },
             WordCross {
-                word: "charlie",
+                word: "char",
+                clue: None,
+                display: None,
                 cross: None,
             },
             WordCross {
                 word: "dalta",
+                clue: None,
+                display: None,
                 cross: Some(CrossData {
                     row: 5,
                     start_point: -1,
@@ -542,6 +1096,8 @@ fn test_compare_crosswords() {
         words: vec![
             WordCross {
                 word: "alpha",
+                clue: None,
+                display: None,
                 cross: Some(CrossData {
                     row: 0,
                     start_point: 5,
@@ -551,6 +1107,8 @@ fn test_compare_crosswords() {
             },
             WordCross {
                 word: "bravoo",
+                clue: None,
+                display: None,
                 cross: Some(CrossData {
                     row: 9,
                     start_point: -2,
@@ -559,11 +1117,15 @@ fn test_compare_crosswords() {
                 }),
             },
             WordCross {
-                word: "charlie",
+                word: "char",
+                clue: None,
+                display: None,
                 cross: None,
             },
             WordCross {
                 word: "dalta",
+                clue: None,
+                display: None,
                 cross: Some(CrossData {
                     row: 5,
                     start_point: -4,
@@ -577,67 +1139,76 @@ fn test_compare_crosswords() {
     let best_crosswords = vec![];
     assert_eq!(
         Comparison::First,
-        compare_crosswords(&crossword_good0, &best_crosswords)
+        compare_crosswords(&crossword_good0, &best_crosswords, &BoundingBox)
     );
     assert_eq!(
         Comparison::First,
-        compare_crosswords(&crossword_good1, &best_crosswords)
+        compare_crosswords(&crossword_good1, &best_crosswords, &BoundingBox)
     );
     assert_eq!(
         Comparison::First,
-        compare_crosswords(&crossword_bad, &best_crosswords)
+        compare_crosswords(&crossword_bad, &best_crosswords, &BoundingBox)
     );
 
     let best_crosswords = vec![crossword_bad.clone()];
     assert_eq!(
         Comparison::Better,
-        compare_crosswords(&crossword_good0, &best_crosswords)
+        compare_crosswords(&crossword_good0, &best_crosswords, &BoundingBox)
     );
     assert_eq!(
         Comparison::Better,
-        compare_crosswords(&crossword_good1, &best_crosswords)
+        compare_crosswords(&crossword_good1, &best_crosswords, &BoundingBox)
     );
 
     let best_crosswords = vec![crossword_good0.clone()];
     assert_eq!(
         Comparison::AsGood,
-        compare_crosswords(&crossword_good1, &best_crosswords)
+        compare_crosswords(&crossword_good1, &best_crosswords, &BoundingBox)
     );
 
     let best_crosswords = vec![crossword_good1.clone()];
     assert_eq!(
         Comparison::AsGood,
-        compare_crosswords(&crossword_good0, &best_crosswords)
+        compare_crosswords(&crossword_good0, &best_crosswords, &BoundingBox)
     );
 
     let best_crosswords = vec![crossword_good0.clone()];
     assert_eq!(
         Comparison::Worse,
-        compare_crosswords(&crossword_bad, &best_crosswords)
+        compare_crosswords(&crossword_bad, &best_crosswords, &BoundingBox)
     );
 
     let best_crosswords = vec![crossword_good1.clone()];
     assert_eq!(
         Comparison::Worse,
-        compare_crosswords(&crossword_bad, &best_crosswords)
+        compare_crosswords(&crossword_bad, &best_crosswords, &BoundingBox)
     );
 
     let best_crosswords = vec![crossword_good0.clone()];
     assert_eq!(
         Comparison::SeedDuplicate,
-        compare_crosswords(&crossword_good0, &best_crosswords)
+        compare_crosswords(&crossword_good0, &best_crosswords, &BoundingBox)
     );
 
     let best_crosswords = vec![crossword_good1.clone()];
     assert_eq!(
         Comparison::SeedDuplicate,
-        compare_crosswords(&crossword_good1, &best_crosswords)
+        compare_crosswords(&crossword_good1, &best_crosswords, &BoundingBox)
     );
 
     let best_crosswords = vec![crossword_bad.clone()];
     assert_eq!(
         Comparison::SeedDuplicate,
-        compare_crosswords(&crossword_bad, &best_crosswords)
+        compare_crosswords(&crossword_bad, &best_crosswords, &BoundingBox)
+    );
+
+    // The same layout, but with a word left which is longer than either side.
+    let mut crossword_long = crossword_good0.clone();
+    crossword_long.words[2].word = "charlie";
+    let best_crosswords = vec![crossword_good1.clone()];
+    assert_eq!(
+        Comparison::Worse,
+        compare_crosswords(&crossword_long, &best_crosswords, &BoundingBox)
     );
 }
 
@@ -732,34 +1303,58 @@ fn test_add_crossword() {
 
     assert_eq!(0, best_crosswords.len());
     assert!(!best_crosswords.contains(&crossword1));
-    add_crossword(Comparison::First, &crossword1, &mut best_crosswords);
+    assert!(add_crossword(
+        Comparison::First,
+        &crossword1,
+        &mut best_crosswords
+    ));
     assert_eq!(1, best_crosswords.len());
     assert!(best_crosswords.contains(&crossword1));
 
     assert_eq!(1, best_crosswords.len());
     assert!(!best_crosswords.contains(&crossword0));
-    add_crossword(Comparison::Worse, &crossword0, &mut best_crosswords);
+    assert!(!add_crossword(
+        Comparison::Worse,
+        &crossword0,
+        &mut best_crosswords
+    ));
     assert_eq!(1, best_crosswords.len());
     assert!(!best_crosswords.contains(&crossword0));
 
     assert_eq!(1, best_crosswords.len());
-    add_crossword(Comparison::SeedDuplicate, &crossword0, &mut best_crosswords);
+    assert!(!add_crossword(
+        Comparison::SeedDuplicate,
+        &crossword0,
+        &mut best_crosswords
+    ));
     assert_eq!(1, best_crosswords.len());
     assert!(!best_crosswords.contains(&crossword0));
 
     assert_eq!(1, best_crosswords.len());
-    add_crossword(Comparison::AsGood, &crossword0, &mut best_crosswords);
+    assert!(add_crossword(
+        Comparison::AsGood,
+        &crossword0,
+        &mut best_crosswords
+    ));
     assert_eq!(2, best_crosswords.len());
     assert!(best_crosswords.contains(&crossword0));
 
     assert_eq!(2, best_crosswords.len());
-    add_crossword(Comparison::Better, &crossword1, &mut best_crosswords);
+    assert!(add_crossword(
+        Comparison::Better,
+        &crossword1,
+        &mut best_crosswords
+    ));
     assert_eq!(1, best_crosswords.len());
     assert!(best_crosswords.contains(&crossword1));
     assert!(!best_crosswords.contains(&crossword0));
 
     assert!(!best_crosswords.contains(&crossword0));
-    add_crossword(Comparison::Better, &crossword0, &mut best_crosswords);
+    assert!(add_crossword(
+        Comparison::Better,
+        &crossword0,
+        &mut best_crosswords
+    ));
     assert_eq!(1, best_crosswords.len());
     assert!(best_crosswords.contains(&crossword0));
 }
@@ -777,6 +1372,8 @@ fn test_remove_word() {
     let mut crossword = Crossword {
         words: vec![WordCross {
             word: "lonesome",
+            clue: None,
+            display: None,
             cross: Some(CrossData {
                 row: 3,
                 start_point: 14,
@@ -786,7 +1383,204 @@ fn test_remove_word() {
         }],
     };
 
+    let mut grid = Grid::new(&crossword);
+
     assert!(crossword.words[word_index].cross != None);
-    remove_word(&word_and_letter, &mut crossword);
+    remove_word(&word_and_letter, &mut crossword, &mut grid);
     assert!(crossword.words[word_index].cross == None);
+    assert_eq!(None, grid.letter([14, 3]));
+    assert_eq!(0, grid.key());
+}
+
+#[test]
+fn test_get_candidates() {
+    let words = vec![
+        "start".to_string(),
+        "at".to_string(),
+        "tall".to_string(),
+        "hat".to_string(),
+    ];
+    let letter_map = letters::get_map(&words);
+    let crossword = crossword::initialise(&words);
+    let grid = Grid::new(&crossword);
+    let candidates = |heuristics: Vec<Heuristic>| {
+        let options = SolverOptions {
+            heuristics,
+            ..SolverOptions::default()
+        };
+        get_candidates(&Search::new(&letter_map, &options), &crossword, &grid)
+            .iter()
+            .map(|(row, mid_point, direction, word_and_letter)| {
+                assert_eq!((0, Direction::Down), (*mid_point, *direction));
+                (*row, words[word_and_letter.word_index].as_str())
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        vec![
+            (1, "at"),
+            (1, "tall"),
+            (1, "hat"),
+            (2, "at"),
+            (2, "tall"),
+            (2, "hat"),
+            (4, "at"),
+            (4, "tall"),
+            (4, "hat"),
+        ],
+        candidates(vec![])
+    );
+    assert_eq!(
+        vec![
+            (1, "tall"),
+            (2, "tall"),
+            (4, "tall"),
+            (1, "hat"),
+            (2, "hat"),
+            (4, "hat"),
+            (1, "at"),
+            (2, "at"),
+            (4, "at"),
+        ],
+        candidates(vec![Heuristic::Longest])
+    );
+    assert_eq!(
+        vec![
+            (2, "at"),
+            (2, "tall"),
+            (2, "hat"),
+            (1, "at"),
+            (1, "tall"),
+            (1, "hat"),
+            (4, "at"),
+            (4, "tall"),
+            (4, "hat"),
+        ],
+        candidates(vec![Heuristic::Central])
+    );
+    assert_eq!(
+        &[(2, "tall"), (2, "hat"), (2, "at"), (1, "tall")],
+        &candidates(vec![Heuristic::Central, Heuristic::Longest])[..4]
+    );
+    assert_eq!(candidates(vec![]), candidates(vec![Heuristic::Connectable]));
+}
+
+#[test]
+fn get_candidates_uncrossable() {
+    let words: Vec<String> = ["hereby", "exist", "words", "for", "sample", "output"]
+        .iter()
+        .map(|word| word.to_string())
+        .collect();
+    let letter_map = letters::get_map(&words);
+    let options = SolverOptions::default();
+    let search = Search::new(&letter_map, &options);
+    let mut crossword = crossword::initialise(&words);
+    let mut grid = Grid::new(&crossword);
+
+    let skipped = helper_check_candidates(&search, &mut crossword, &mut grid, words.len());
+
+    assert!(skipped > 0);
+}
+
+#[test]
+fn test_get_connectivity() {
+    let words = vec![
+        "start".to_string(),
+        "at".to_string(),
+        "tall".to_string(),
+        "hat".to_string(),
+        "z".to_string(),
+    ];
+
+    assert_eq!(
+        vec![9, 7, 7, 7, 0],
+        get_connectivity(&letters::get_map(&words))
+    );
+}
+
+/// Tries every unplaced word across every placed letter, down to `depth` more words, checking
+/// that each insertion which succeeds is among the candidates. Returns how many insertions the
+/// candidates left out.
+fn helper_check_candidates<'a>(
+    search: &Search<'a>,
+    crossword: &mut Crossword<'a>,
+    grid: &mut Grid,
+    depth: usize,
+) -> usize {
+    let kept: Vec<_> = get_candidates(search, crossword, grid)
+        .into_iter()
+        .map(|(row, mid_point, direction, word_and_letter)| {
+            let letter = (word_and_letter.word_index, word_and_letter.letter_index);
+            (row, mid_point, direction, letter)
+        })
+        .collect();
+
+    let mut unfiltered = Vec::new();
+    for (letter, row, mid_point, direction) in crossword.get_crossable_letters() {
+        for word_and_letter in search.letter_map.get(&letter).into_iter().flatten() {
+            if crossword.words[word_and_letter.word_index].cross.is_none() {
+                unfiltered.push((row, mid_point, direction, word_and_letter));
+            }
+        }
+    }
+
+    let mut skipped = 0;
+    for (row, mid_point, direction, word_and_letter) in unfiltered {
+        let letter = (word_and_letter.word_index, word_and_letter.letter_index);
+        let candidate = (row, mid_point, direction, letter);
+        if insert_word(
+            row,
+            mid_point,
+            direction,
+            word_and_letter,
+            search.options,
+            crossword,
+            grid,
+        ) {
+            assert!(kept.contains(&candidate));
+            if depth > 1 {
+                skipped += helper_check_candidates(search, crossword, grid, depth - 1);
+            }
+            remove_word(word_and_letter, crossword, grid);
+        } else if !kept.contains(&candidate) {
+            skipped += 1;
+        }
+    }
+
+    skipped
+}
+
+/// Places the words, given with their row, start point and direction, and checks whether the last
+/// can then be inserted.
+fn helper_check_insertable(
+    placed: &[(&str, i32, i32, Direction)],
+    (word, row, start_point, direction): (&str, i32, i32, Direction),
+) -> bool {
+    let mut words: Vec<String> = placed.iter().map(|(word, ..)| word.to_string()).collect();
+    words.push(word.to_string());
+    let mut crossword = crossword::initialise_empty(&words);
+    for (word_index, (_, row, start_point, direction)) in placed.iter().enumerate() {
+        crossword.words[word_index].cross = Some(CrossData {
+            row: *row,
+            start_point: *start_point,
+            direction: *direction,
+            order: word_index,
+        });
+    }
+    let word_and_letter = WordAndLetter {
+        word_index: placed.len(),
+        word,
+        letter: word.chars().next().unwrap(),
+        letter_index: 0,
+        n_letters_after: word.chars().count() - 1,
+    };
+
+    check_insertable(
+        row,
+        start_point,
+        direction,
+        &word_and_letter,
+        &Grid::new(&crossword),
+    )
 }
@@ -0,0 +1,140 @@
+use crate::crossword::{CrossData, Direction, WordCross};
+
+use super::*;
+
+#[test]
+fn objectives_new_and_bounds() {
+    let crossword = helper_get_crossword(4, -2);
+
+    assert_eq!(
+        Objectives {
+            width: 5,
+            height: 5,
+            crossings: 0
+        },
+        Objectives::new(&crossword, false)
+    );
+    assert_eq!(
+        Objectives {
+            width: 5,
+            height: 5,
+            crossings: 1
+        },
+        Objectives::new(&crossword, true)
+    );
+    assert_eq!(
+        vec![Objectives {
+            width: 5,
+            height: 5,
+            crossings: 4
+        }],
+        Objectives::bounds(&crossword, true)
+    );
+
+    let mut crossword = crossword;
+    crossword.words[2].word = "avocados";
+    assert_eq!(
+        vec![
+            Objectives {
+                width: 8,
+                height: 5,
+                crossings: 0
+            },
+            Objectives {
+                width: 5,
+                height: 8,
+                crossings: 0
+            }
+        ],
+        Objectives::bounds(&crossword, false)
+    );
+}
+
+#[test]
+fn objectives_dominates() {
+    let square = Objectives {
+        width: 10,
+        height: 10,
+        crossings: 3,
+    };
+    let wide = Objectives {
+        width: 12,
+        height: 8,
+        crossings: 3,
+    };
+    let large = Objectives {
+        width: 12,
+        height: 10,
+        crossings: 3,
+    };
+    let large_crossed = Objectives {
+        width: 12,
+        height: 10,
+        crossings: 5,
+    };
+
+    assert!(!square.dominates(&wide));
+    assert!(!wide.dominates(&square));
+    assert!(square.dominates(&large));
+    assert!(wide.dominates(&large));
+    assert!(!square.dominates(&large_crossed));
+    assert!(!square.dominates(&square));
+}
+
+#[test]
+fn test_front() {
+    let wide = helper_get_crossword(2, 1);
+    let tall = helper_get_crossword(6, 0);
+    let large = helper_get_crossword(6, 3);
+
+    assert!(is_dominated(
+        &Objectives::new(&large, false),
+        std::slice::from_ref(&wide),
+        false
+    ));
+    assert!(!is_dominated(
+        &Objectives::new(&tall, false),
+        std::slice::from_ref(&wide),
+        false
+    ));
+
+    assert_eq!(
+        vec![wide.clone(), tall.clone()],
+        front(vec![wide, large, tall], false)
+    );
+}
+
+fn helper_get_crossword(bravo_row: i32, bravo_start_point: i32) -> Crossword<'static> {
+    Crossword {
+        words: vec![
+            WordCross {
+                word: "alpha",
+                clue: None,
+                display: None,
+                cross: Some(CrossData {
+                    row: 0,
+                    start_point: 0,
+                    direction: Direction::Down,
+                    order: 0,
+                }),
+            },
+            WordCross {
+                word: "bravo",
+                clue: None,
+                display: None,
+                cross: Some(CrossData {
+                    row: bravo_row,
+                    start_point: bravo_start_point,
+                    direction: Direction::Across,
+                    order: 1,
+                }),
+            },
+            WordCross {
+                word: "oak",
+                clue: None,
+                display: None,
+                cross: None,
+            },
+        ],
+    }
+}
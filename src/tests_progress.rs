@@ -0,0 +1,68 @@
+use std::sync::atomic::AtomicUsize;
+
+use super::*;
+
+#[test]
+fn statistics_display() {
+    let mut statistics = Statistics {
+        nodes: 1200,
+        rejected: 300,
+        pruned: 40,
+        duplicates: 5,
+        best_size: None,
+        elapsed: Duration::from_millis(1250),
+    };
+    assert_eq!(
+        "1200 nodes, 300 rejected, 40 pruned, 5 duplicates, nothing found yet, 1.2s",
+        statistics.to_string()
+    );
+
+    statistics.best_size = Some((6, 7));
+    assert_eq!(
+        "1200 nodes, 300 rejected, 40 pruned, 5 duplicates, best 6 x 7, 1.2s",
+        statistics.to_string()
+    );
+}
+
+#[test]
+fn counters_statistics() {
+    let counters = Counters::new();
+    assert_eq!(1, Counters::count(&counters.nodes));
+    assert_eq!(2, Counters::count(&counters.nodes));
+    Counters::count(&counters.pruned);
+    counters.set_smaller_size((4, 5));
+    counters.set_smaller_size((6, 6));
+    counters.set_smaller_size((3, 6));
+
+    let statistics = counters.statistics();
+
+    assert_eq!(2, statistics.nodes);
+    assert_eq!(0, statistics.rejected);
+    assert_eq!(1, statistics.pruned);
+    assert_eq!(0, statistics.duplicates);
+    assert_eq!(Some((3, 6)), statistics.best_size);
+
+    counters.set_best_size((6, 6));
+    assert_eq!(Some((6, 6)), counters.statistics().best_size);
+}
+
+#[test]
+fn counters_report() {
+    let reports = Arc::new(AtomicUsize::new(0));
+    let counted = reports.clone();
+    let progress = Progress::new(Duration::ZERO, move |_| {
+        counted.fetch_add(1, Ordering::Relaxed);
+    });
+    let counters = Counters::new();
+
+    counters.report(&progress);
+    counters.report(&progress);
+    assert_eq!(2, reports.load(Ordering::Relaxed));
+
+    let progress = Progress {
+        interval: Duration::from_secs(3600),
+        ..progress
+    };
+    counters.report(&progress);
+    assert_eq!(2, reports.load(Ordering::Relaxed));
+}
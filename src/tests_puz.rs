@@ -0,0 +1,188 @@
+use crate::clues::Clue;
+use crate::crossword::Direction;
+use crate::solver::{Solver, SolverOptions};
+use crate::tests_helpers::helper_word;
+
+use super::*;
+
+#[test]
+fn test_checksum() {
+    assert_eq!(0, checksum(&[], 0));
+    assert_eq!(0x4074, checksum(b"ABC", 0));
+    assert_eq!(
+        helper_checksum(b"ACROSS&DOWN", 0x1234),
+        checksum(b"ACROSS&DOWN", 0x1234)
+    );
+}
+
+#[test]
+fn crossword_to_puz() {
+    let clues = [
+        Clue {
+            text: "Preposition".to_string(),
+            enumeration: None,
+        },
+        Clue {
+            text: "Belonging to".to_string(),
+            enumeration: Some("(2)".to_string()),
+        },
+    ];
+    let mut crossword = helper_crossword();
+    crossword.words[0].clue = Some(&clues[0]);
+    crossword.words[1].clue = Some(&clues[1]);
+    let info = PuzInfo {
+        title: "Tiny".to_string(),
+        author: "Émile".to_string(),
+        ..PuzInfo::default()
+    };
+
+    let puz = to_puz(&crossword, &info).unwrap();
+
+    assert_eq!(MAGIC, &puz[0x02..0x0E]);
+    assert_eq!(VERSION, &puz[0x18..0x1C]);
+    assert_eq!([3, 2], puz[0x2C..0x2E]);
+    assert_eq!([2, 0], puz[0x2E..0x30]);
+    assert_eq!(b"O..FOR", &puz[0x34..0x3A]);
+    assert_eq!(b"-..---", &puz[0x3A..0x40]);
+    assert_eq!(
+        b"Tiny\0\xC9mile\0\0Belonging to (2)\0Preposition\0\0",
+        &puz[0x40..]
+    );
+    helper_verify_checksums(&puz);
+}
+
+#[test]
+fn solution_to_puz() {
+    let solver = Solver::new(
+        &["hereby", "exist", "words", "for", "sample", "output"],
+        SolverOptions::default(),
+    );
+
+    for crossword in solver.solve().crosswords {
+        let puz = to_puz(&crossword, &PuzInfo::default()).unwrap();
+        let (width, height) = crossword.get_width_height();
+
+        assert_eq!([width as u8, height as u8], puz[0x2C..0x2E]);
+        assert_eq!([6, 0], puz[0x2E..0x30]);
+        assert_eq!(HEADER_SIZE + 2 * width * height + 3 + 6 + 1, puz.len());
+        helper_verify_checksums(&puz);
+    }
+}
+
+#[test]
+fn to_puz_errors() {
+    let crossword = Crossword {
+        words: vec![helper_word("дом", 0, 0, Direction::Across)],
+    };
+    assert_eq!(
+        Err(PuzError::Unencodable('Д')),
+        to_puz(&crossword, &PuzInfo::default())
+    );
+
+    let info = PuzInfo {
+        title: "☃".to_string(),
+        ..PuzInfo::default()
+    };
+    assert_eq!(
+        Err(PuzError::Unencodable('☃')),
+        to_puz(&helper_crossword(), &info)
+    );
+
+    let long_word = "a".repeat(256);
+    let crossword = Crossword {
+        words: vec![helper_word(&long_word, 0, 0, Direction::Down)],
+    };
+    assert_eq!(
+        Err(PuzError::TooLarge(1, 256)),
+        to_puz(&crossword, &PuzInfo::default())
+    );
+}
+
+#[test]
+fn to_puz_keeps_letters_without_single_capital() {
+    let crossword = Crossword {
+        words: vec![helper_word("maß", 0, 0, Direction::Across)],
+    };
+
+    let puz = to_puz(&crossword, &PuzInfo::default()).unwrap();
+
+    assert_eq!(b"MA\xDF", &puz[0x34..0x37]);
+}
+
+/// Recomputes every checksum in the file, following the published description of the format.
+fn helper_verify_checksums(puz: &[u8]) {
+    let width = puz[0x2C] as usize;
+    let height = puz[0x2D] as usize;
+    let n_clues = u16::from_le_bytes([puz[0x2E], puz[0x2F]]) as usize;
+    let solution = &puz[0x34..0x34 + width * height];
+    let grid = &puz[0x34 + width * height..0x34 + 2 * width * height];
+
+    let mut strings = puz[0x34 + 2 * width * height..].split(|byte| *byte == 0);
+    let title = strings.next().unwrap();
+    let author = strings.next().unwrap();
+    let copyright = strings.next().unwrap();
+    let clues: Vec<&[u8]> = (0..n_clues).map(|_| strings.next().unwrap()).collect();
+    let notes = strings.next().unwrap();
+
+    let strings_checksum = |start: u16| {
+        let mut sum = start;
+        for string in [title, author, copyright] {
+            if !string.is_empty() {
+                sum = helper_checksum(string, sum);
+                sum = helper_checksum(&[0], sum);
+            }
+        }
+        for clue in &clues {
+            sum = helper_checksum(clue, sum);
+        }
+        if !notes.is_empty() {
+            sum = helper_checksum(notes, sum);
+            sum = helper_checksum(&[0], sum);
+        }
+        sum
+    };
+
+    let cib = helper_checksum(&puz[0x2C..0x34], 0);
+    assert_eq!(cib, u16::from_le_bytes([puz[0x0E], puz[0x0F]]));
+
+    let mut overall = helper_checksum(solution, cib);
+    overall = helper_checksum(grid, overall);
+    overall = strings_checksum(overall);
+    assert_eq!(overall, u16::from_le_bytes([puz[0x00], puz[0x01]]));
+
+    let parts = [
+        cib,
+        helper_checksum(solution, 0),
+        helper_checksum(grid, 0),
+        strings_checksum(0),
+    ];
+    for (index, part) in parts.iter().enumerate() {
+        assert_eq!(b"ICHEATED"[index] ^ (part & 0xFF) as u8, puz[0x10 + index]);
+        assert_eq!(
+            b"ICHEATED"[index + 4] ^ (part >> 8) as u8,
+            puz[0x14 + index]
+        );
+    }
+}
+
+fn helper_checksum(data: &[u8], start: u16) -> u16 {
+    let mut sum = start;
+    for byte in data {
+        if sum & 1 == 1 {
+            sum = (sum >> 1) + 0x8000;
+        } else {
+            sum >>= 1;
+        }
+        sum = sum.wrapping_add(*byte as u16);
+    }
+    sum
+}
+
+fn helper_crossword() -> Crossword<'static> {
+    Crossword {
+        words: vec![
+            helper_word("for", 0, 0, Direction::Across),
+            helper_word("of", 0, -1, Direction::Down),
+        ],
+    }
+}
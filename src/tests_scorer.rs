@@ -0,0 +1,107 @@
+use crate::crossword::{CrossData, Direction, WordCross};
+
+use super::*;
+
+#[test]
+fn bounding_box() {
+    let crossword = helper_get_generic_crossword();
+    assert_eq!(7_000_005.0, BoundingBox.score(&crossword));
+    assert_eq!(7_000_005.0, BoundingBox.bound(&crossword));
+}
+
+#[test]
+fn area_and_perimeter() {
+    let crossword = helper_get_generic_crossword();
+    assert_eq!(35.0, Area.score(&crossword));
+    assert_eq!(35.0, Area.bound(&crossword));
+    assert_eq!(24.0, Perimeter.score(&crossword));
+    assert_eq!(24.0, Perimeter.bound(&crossword));
+}
+
+#[test]
+fn size_bounds_with_long_word_left() {
+    let mut crossword = helper_get_generic_crossword();
+    crossword.words[2].word = "charlotte";
+    assert_eq!(9_000_005.0, BoundingBox.bound(&crossword));
+    assert_eq!(45.0, Area.bound(&crossword));
+    assert_eq!(28.0, Perimeter.bound(&crossword));
+}
+
+#[test]
+fn aspect_ratio() {
+    let crossword = helper_get_generic_crossword();
+    assert!(AspectRatio { target: 1.4 }.score(&crossword).abs() < 1e-9);
+    assert!(AspectRatio { target: 1.0 / 1.4 }.score(&crossword).abs() < 1e-9);
+    assert!((AspectRatio { target: 1.0 }.score(&crossword) - 1.4_f64.ln()).abs() < 1e-9);
+    assert_eq!(0.0, AspectRatio { target: 1.0 }.bound(&crossword));
+}
+
+#[test]
+fn crossings() {
+    let crossword = helper_get_generic_crossword();
+    assert_eq!(-2.0, Crossings.score(&crossword));
+    assert_eq!(-9.0, Crossings.bound(&crossword));
+}
+
+#[test]
+fn density() {
+    let crossword = helper_get_generic_crossword();
+    assert_eq!(-13.0 / 35.0, Density.score(&crossword));
+    assert_eq!(-1.0, Density.bound(&crossword));
+}
+
+#[test]
+fn weighted() {
+    let crossword = helper_get_generic_crossword();
+    let scorer = Weighted {
+        scorers: vec![(2.0, Arc::new(Area)), (1.0, Arc::new(Crossings))],
+    };
+    assert_eq!(68.0, scorer.score(&crossword));
+    assert_eq!(61.0, scorer.bound(&crossword));
+}
+
+fn helper_get_generic_crossword() -> Crossword<'static> {
+    Crossword {
+        words: vec![
+            WordCross {
+                word: "alpha",
+                clue: None,
+                display: None,
+                cross: Some(CrossData {
+                    row: 5,
+                    start_point: 6,
+                    direction: Direction::Down,
+                    order: 1,
+                }),
+            },
+            WordCross {
+                word: "bravo",
+                clue: None,
+                display: None,
+                cross: Some(CrossData {
+                    row: 6,
+                    start_point: 3,
+                    direction: Direction::Across,
+                    order: 0,
+                }),
+            },
+            WordCross {
+                word: "charlie",
+                clue: None,
+                display: None,
+                cross: None,
+            },
+            WordCross {
+                word: "delta",
+                clue: None,
+                display: None,
+                cross: Some(CrossData {
+                    row: 10,
+                    start_point: 1,
+                    direction: Direction::Across,
+                    order: 2,
+                }),
+            },
+        ],
+    }
+}
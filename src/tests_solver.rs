@@ -0,0 +1,797 @@
+use std::sync::Mutex;
+
+use crate::crossword::CanonicalForm;
+use crate::scorer::{Area, Crossings};
+
+use super::*;
+
+#[test]
+fn solver_new() {
+    let solver = Solver::new(&["Small", "COLLECTION"], SolverOptions::default());
+
+    assert_eq!(
+        &["small".to_string(), "collection".to_string()],
+        solver.words()
+    );
+}
+
+#[test]
+fn solver_solve() {
+    let solver = Solver::new(
+        &["small", "collection", "of", "words"],
+        SolverOptions::default(),
+    );
+
+    let solution = solver.solve();
+    let best_crosswords = &solution.crosswords;
+
+    assert!(solution.exhaustive);
+    assert_eq!(1, best_crosswords.len());
+    assert!(best_crosswords[0].all_words_crossed());
+    assert_eq!((5, 10), best_crosswords[0].get_min_max());
+}
+
+#[test]
+fn solver_solve_threads() {
+    let words = ["hereby", "exist", "words", "for", "sample", "output"];
+    let solver = Solver::new(&words, SolverOptions::default());
+    let best_crosswords = solver.solve().crosswords;
+
+    for threads in [0, 2, 4] {
+        let options = SolverOptions {
+            threads,
+            ..SolverOptions::default()
+        };
+        let threaded_solver = Solver::new(&words, options);
+        assert_eq!(best_crosswords, threaded_solver.solve().crosswords);
+    }
+}
+
+#[test]
+fn solver_solve_scorer() {
+    let words = ["hereby", "exist", "words", "for", "sample", "output"];
+    let solver = Solver::new(&words, SolverOptions::default());
+    let default_solution = solver.solve();
+
+    let options = SolverOptions {
+        scorer: Arc::new(Area),
+        ..SolverOptions::default()
+    };
+    let area_solver = Solver::new(&words, options);
+    let area_solution = area_solver.solve();
+
+    let options = SolverOptions {
+        scorer: Arc::new(Crossings),
+        ..SolverOptions::default()
+    };
+    let crossings_solver = Solver::new(&words, options);
+    let crossings_solution = crossings_solver.solve();
+
+    assert!(!area_solution.crosswords.is_empty());
+    assert!(!crossings_solution.crosswords.is_empty());
+    for crossword in &default_solution.crosswords {
+        assert!(Area.score(&area_solution.crosswords[0]) <= Area.score(crossword));
+        assert!(Crossings.score(&crossings_solution.crosswords[0]) <= Crossings.score(crossword));
+    }
+}
+
+#[test]
+fn max_size_fits() {
+    let max_size = MaxSize {
+        width: 15,
+        height: 10,
+        rotatable: false,
+    };
+    assert!(max_size.fits(15, 10));
+    assert!(max_size.fits(3, 4));
+    assert!(!max_size.fits(16, 10));
+    assert!(!max_size.fits(10, 15));
+
+    let max_size = MaxSize {
+        rotatable: true,
+        ..max_size
+    };
+    assert!(max_size.fits(10, 15));
+    assert!(!max_size.fits(11, 15));
+}
+
+#[test]
+fn solver_solve_max_size() {
+    let words = ["planet", "orange", "violet", "marble", "silver", "copper"];
+
+    for (width, height, rotatable) in [(13, 6, false), (6, 13, false), (6, 13, true)] {
+        let max_size = MaxSize {
+            width,
+            height,
+            rotatable,
+        };
+        let options = SolverOptions {
+            max_size: Some(max_size),
+            ..SolverOptions::default()
+        };
+        let solver = Solver::new(&words, options);
+
+        let solution = solver.solve();
+
+        assert!(!solution.crosswords.is_empty());
+        for crossword in &solution.crosswords {
+            let (width, height) = crossword.get_width_height();
+            assert!(max_size.fits(width, height));
+        }
+    }
+
+    let options = SolverOptions {
+        max_size: Some(MaxSize {
+            width: 6,
+            height: 6,
+            rotatable: true,
+        }),
+        ..SolverOptions::default()
+    };
+    let solver = Solver::new(&words, options);
+
+    let solution = solver.solve();
+
+    assert!(solution.crosswords.is_empty());
+    assert!(solution.exhaustive);
+}
+
+#[test]
+fn solver_solve_max_size_first_word() {
+    let options = SolverOptions {
+        max_size: Some(MaxSize {
+            width: 5,
+            height: 5,
+            rotatable: true,
+        }),
+        ..SolverOptions::default()
+    };
+
+    for words in [&["extraordinary"][..], &["extraordinary", "dine"]] {
+        let solver = Solver::new(words, options.clone());
+        let solution = solver.solve();
+
+        assert!(solution.crosswords.is_empty());
+        assert!(solution.exhaustive);
+    }
+}
+
+#[test]
+fn solver_with_seeds() {
+    let words = ["hereby", "exist", "words", "for", "sample", "output"];
+    let solver = Solver::new(&words, SolverOptions::default());
+    let default_solution = solver.solve().crosswords;
+
+    let seeds = [Seed {
+        word: "Hereby".to_string(),
+        row: 0,
+        start_point: 0,
+        direction: Direction::Across,
+    }];
+    let solver = Solver::new(&words, SolverOptions::default())
+        .with_seeds(&seeds)
+        .unwrap();
+    assert_eq!(default_solution, solver.solve().crosswords);
+
+    let seeds = [
+        Seed {
+            word: "sample".to_string(),
+            row: 2,
+            start_point: 0,
+            direction: Direction::Down,
+        },
+        Seed {
+            word: "exist".to_string(),
+            row: 5,
+            start_point: 2,
+            direction: Direction::Across,
+        },
+    ];
+    let solver = Solver::new(&words, SolverOptions::default())
+        .with_seeds(&seeds)
+        .unwrap();
+
+    let solution = solver.solve();
+
+    assert!(!solution.crosswords.is_empty());
+    for crossword in &solution.crosswords {
+        assert!(crossword.all_words_crossed());
+        assert_eq!(
+            Some(CrossData {
+                row: 2,
+                start_point: 0,
+                direction: Direction::Down,
+                order: 0,
+            }),
+            crossword.words[4].cross
+        );
+        assert_eq!(
+            Some(CrossData {
+                row: 5,
+                start_point: 2,
+                direction: Direction::Across,
+                order: 1,
+            }),
+            crossword.words[1].cross
+        );
+    }
+}
+
+#[test]
+fn solver_with_seeds_linked_last() {
+    // The Across seeds only touch at a corner, until "bb" joins them.
+    let seeds: Vec<Seed> = [
+        ("ab", 0, 0, Direction::Across),
+        ("bc", 1, 1, Direction::Across),
+        ("bb", 1, 0, Direction::Down),
+    ]
+    .iter()
+    .map(|(word, row, start_point, direction)| Seed {
+        word: word.to_string(),
+        row: *row,
+        start_point: *start_point,
+        direction: *direction,
+    })
+    .collect();
+    let solver = Solver::new(&["ab", "bc", "bb"], SolverOptions::default())
+        .with_seeds(&seeds)
+        .unwrap();
+
+    let crosswords = solver.solve().crosswords;
+
+    assert_eq!(1, crosswords.len());
+    for (word, seed) in crosswords[0].words.iter().zip(&seeds) {
+        let cross_data = word.cross.as_ref().unwrap();
+        assert_eq!(
+            (seed.row, seed.start_point, seed.direction),
+            (cross_data.row, cross_data.start_point, cross_data.direction)
+        );
+    }
+}
+
+#[test]
+fn solver_with_seeds_joined_rows() {
+    // Each layout has two Across words in neighbouring rows, which can only both be placed once
+    // the Down word joining them is, whichever order the seeds are given in.
+    let layouts = [
+        [
+            ("bcdef", 0, 1, Direction::Across),
+            ("bghik", 1, 0, Direction::Down),
+            ("jklm", 4, 0, Direction::Across),
+            ("mr", 3, 4, Direction::Down),
+            ("rstu", 5, 3, Direction::Across),
+            ("fnopqt", 5, 0, Direction::Down),
+        ],
+        [
+            ("mrstq", 5, 1, Direction::Across),
+            ("bijklm", 1, 0, Direction::Down),
+            ("gnopq", 5, 1, Direction::Down),
+            ("abcd", 0, 0, Direction::Across),
+            ("efgh", 1, 3, Direction::Across),
+            ("de", 3, 0, Direction::Down),
+        ],
+    ];
+
+    for layout in layouts {
+        let words: Vec<&str> = layout.iter().map(|(word, ..)| *word).collect();
+        for reversed in [false, true] {
+            let mut seeds: Vec<Seed> = layout
+                .iter()
+                .map(|(word, row, start_point, direction)| Seed {
+                    word: word.to_string(),
+                    row: *row,
+                    start_point: *start_point,
+                    direction: *direction,
+                })
+                .collect();
+            if reversed {
+                seeds.reverse();
+            }
+            let solver = Solver::new(&words, SolverOptions::default())
+                .with_seeds(&seeds)
+                .unwrap();
+
+            let crosswords = solver.solve().crosswords;
+
+            assert_eq!(1, crosswords.len());
+            for (word, (_, row, start_point, direction)) in crosswords[0].words.iter().zip(&layout)
+            {
+                let cross_data = word.cross.as_ref().unwrap();
+                assert_eq!(
+                    (*row, *start_point, *direction),
+                    (cross_data.row, cross_data.start_point, cross_data.direction)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn solver_with_seeds_errors() {
+    let words = ["hereby", "exist", "words"];
+    let seed = Seed {
+        word: "hereby".to_string(),
+        row: 0,
+        start_point: 0,
+        direction: Direction::Across,
+    };
+
+    let clashing_seed = Seed {
+        word: "exist".to_string(),
+        row: 1,
+        start_point: 0,
+        direction: Direction::Across,
+    };
+    let solver = Solver::new(&words, SolverOptions::default());
+    assert_eq!(
+        Some(SeedError::Unplaceable("exist".to_string())),
+        solver.with_seeds(&[seed.clone(), clashing_seed]).err()
+    );
+
+    let distant_seed = Seed {
+        word: "exist".to_string(),
+        row: 5,
+        start_point: 0,
+        direction: Direction::Across,
+    };
+    let solver = Solver::new(&words, SolverOptions::default());
+    assert_eq!(
+        Some(SeedError::Disconnected),
+        solver.with_seeds(&[seed.clone(), distant_seed]).err()
+    );
+
+    let options = SolverOptions {
+        max_size: Some(MaxSize {
+            width: 5,
+            height: 5,
+            rotatable: false,
+        }),
+        ..SolverOptions::default()
+    };
+    let solver = Solver::new(&words, options);
+    assert_eq!(
+        Some(SeedError::Unplaceable("hereby".to_string())),
+        solver.with_seeds(&[seed]).err()
+    );
+}
+
+#[test]
+fn solver_with_seeds_new_word() {
+    let seeds = [
+        Seed {
+            word: "for".to_string(),
+            row: 0,
+            start_point: 0,
+            direction: Direction::Across,
+        },
+        Seed {
+            word: "of".to_string(),
+            row: 1,
+            start_point: 0,
+            direction: Direction::Down,
+        },
+    ];
+    let solver = Solver::new(&["for"], SolverOptions::default())
+        .with_seeds(&seeds)
+        .unwrap();
+
+    assert_eq!(&["for".to_string(), "of".to_string()], solver.words());
+
+    let solution = solver.solve();
+
+    assert_eq!(1, solution.crosswords.len());
+    assert_eq!((2, 3), solution.crosswords[0].get_min_max());
+}
+
+#[test]
+fn solver_solve_no_words() {
+    let words: [&str; 0] = [];
+    let solver = Solver::new(&words, SolverOptions::default());
+
+    assert!(solver.solve().crosswords.is_empty());
+}
+
+#[test]
+fn solver_solve_no_crossword() {
+    let solver = Solver::new(&["abc", "xyz"], SolverOptions::default());
+
+    let solution = solver.solve();
+
+    assert!(solution.crosswords.is_empty());
+    assert!(solution.exhaustive);
+}
+
+#[test]
+fn solver_solve_time_limit() {
+    let words = ["hereby", "exist", "words", "for", "sample", "output"];
+
+    for threads in [1, 2] {
+        let options = SolverOptions {
+            threads,
+            time_limit: Some(Duration::ZERO),
+            ..SolverOptions::default()
+        };
+        let solver = Solver::new(&words, options);
+
+        let solution = solver.solve();
+
+        assert!(!solution.exhaustive);
+        assert!(solution.crosswords.is_empty());
+    }
+}
+
+#[test]
+fn solver_solve_cancel() {
+    let words = ["hereby", "exist", "words", "for", "sample", "output"];
+    let cancel = CancelToken::new();
+    let options = SolverOptions {
+        cancel: Some(cancel.clone()),
+        ..SolverOptions::default()
+    };
+    let solver = Solver::new(&words, options);
+
+    assert!(solver.solve().exhaustive);
+
+    cancel.cancel();
+    assert!(cancel.is_cancelled());
+    assert!(!solver.solve().exhaustive);
+}
+
+#[test]
+fn solver_with_clues() {
+    let clue = Clue {
+        text: "In this way".to_string(),
+        enumeration: None,
+    };
+    let solver = Solver::new(&["hereby", "exist", "words"], SolverOptions::default())
+        .with_clues(vec![Some(clue.clone())]);
+
+    let solution = solver.solve();
+
+    assert!(!solution.crosswords.is_empty());
+    for crossword in &solution.crosswords {
+        assert_eq!(Some(&clue), crossword.words[0].clue);
+        assert_eq!(None, crossword.words[1].clue);
+        assert_eq!(None, crossword.words[2].clue);
+    }
+}
+
+#[test]
+fn solver_solve_non_ascii_words() {
+    for words in [
+        vec!["café", "été", "crème"],
+        vec!["дом", "мир", "ром"],
+        vec!["μέλι", "λόγος", "γάλα"],
+    ] {
+        let solver = Solver::new(&words, SolverOptions::default());
+
+        let solution = solver.solve();
+
+        assert!(!solution.crosswords.is_empty(), "{:?}", words);
+        for crossword in &solution.crosswords {
+            assert!(crossword.all_words_crossed());
+            assert!(crossword.is_valid());
+            let letters = crossword.get_letters();
+            let (width, height) = crossword.get_width_height();
+            let [x_low, y_low] = crossword.get_origin();
+            assert!(letters.keys().all(|[x, y]| {
+                (x_low..x_low + width as i32).contains(x)
+                    && (y_low..y_low + height as i32).contains(y)
+            }));
+            assert_eq!(crossword.count_cells(), letters.len());
+            let grid = crossword.to_string();
+            assert_eq!(height, grid.lines().count());
+            assert!(grid.lines().all(|line| line.chars().count() == width));
+            assert_eq!(
+                crossword.count_cells(),
+                grid.chars().filter(|cell| !cell.is_whitespace()).count()
+            );
+        }
+    }
+}
+
+#[test]
+fn solver_strip_diacritics() {
+    let words = ["crème", "Été"];
+    assert!(Solver::new(&words, SolverOptions::default())
+        .solve()
+        .crosswords
+        .is_empty());
+
+    let mut options = SolverOptions::default();
+    options.normalisation.strip_diacritics = true;
+    let seeds = [Seed {
+        word: "Crème".to_string(),
+        row: 0,
+        start_point: 0,
+        direction: Direction::Across,
+    }];
+    let solver = Solver::new(&words, options).with_seeds(&seeds).unwrap();
+
+    assert_eq!(&["creme".to_string(), "ete".to_string()], solver.words());
+
+    let solution = solver.solve();
+
+    assert!(!solution.crosswords.is_empty());
+    for crossword in &solution.crosswords {
+        assert_eq!(Some("crème"), crossword.words[0].display);
+        assert_eq!(Some("Été"), crossword.words[1].display);
+        assert!(crossword.all_words_crossed());
+    }
+}
+
+#[test]
+fn solver_digraph_cells() {
+    let mut options = SolverOptions::default();
+    options.normalisation.digraphs = vec!["ll".to_string()];
+    assert!(Solver::new(&["llan", "lol"], options.clone())
+        .solve()
+        .crosswords
+        .is_empty());
+
+    let solver = Solver::new(&["Llan", "allt"], options);
+    let solution = solver.solve();
+
+    assert_eq!(
+        &[3, 3],
+        &solver
+            .words()
+            .iter()
+            .map(|word| word.chars().count())
+            .collect::<Vec<_>>()[..]
+    );
+    assert!(!solution.crosswords.is_empty());
+    let ll = letters::get_digraph_cell("ll").unwrap();
+    for crossword in &solution.crosswords {
+        assert_eq!((3, 3), crossword.get_width_height());
+        assert_eq!(5, crossword.count_cells());
+        assert_eq!(None, crossword.words[0].display);
+        assert_eq!("llan", letters::to_display(crossword.words[0].word));
+        assert!(crossword.get_letters().values().any(|cell| *cell == ll));
+    }
+}
+
+#[test]
+fn solver_drop_separators() {
+    let mut options = SolverOptions::default();
+    options.normalisation.drop_separators = true;
+
+    let solver = Solver::new(&["ice cream", "acre"], options);
+    let solution = solver.solve();
+
+    assert!(!solution.crosswords.is_empty());
+    for crossword in &solution.crosswords {
+        assert_eq!("icecream", crossword.words[0].word);
+        assert_eq!(Some("ice cream"), crossword.words[0].display);
+        assert_eq!((4, 8), crossword.get_min_max());
+    }
+}
+
+#[test]
+fn solver_solve_statistics() {
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let reported = reports.clone();
+    let options = SolverOptions {
+        progress: Some(Progress::new(Duration::ZERO, move |statistics| {
+            reported.lock().unwrap().push(statistics.clone());
+        })),
+        ..SolverOptions::default()
+    };
+    let solver = Solver::new(
+        &[
+            "hereby", "exist", "words", "for", "sample", "output", "planet",
+        ],
+        options,
+    );
+
+    let solution = solver.solve();
+    let statistics = &solution.statistics;
+
+    assert!(statistics.nodes > 0);
+    assert!(statistics.rejected > 0);
+    assert!(statistics.pruned > 0);
+    assert_eq!(
+        Some(solution.crosswords[0].get_width_height()),
+        statistics.best_size
+    );
+    let reports = reports.lock().unwrap();
+    assert!(reports.len() > 1);
+    assert_eq!(statistics, reports.last().unwrap());
+    assert!(reports
+        .windows(2)
+        .all(|pair| pair[0].nodes <= pair[1].nodes && pair[0].elapsed <= pair[1].elapsed));
+}
+
+#[test]
+fn solver_solve_statistics_threads() {
+    let words = ["hereby", "exist", "words", "for", "sample", "output"];
+    let options = SolverOptions {
+        threads: 4,
+        ..SolverOptions::default()
+    };
+
+    let statistics = Solver::new(&words, options).solve().statistics;
+
+    assert!(statistics.nodes > 0);
+    assert!(statistics.best_size.is_some());
+    assert_eq!(
+        Statistics::default().best_size,
+        Solver::new(&["abc", "xyz"], SolverOptions::default())
+            .solve()
+            .statistics
+            .best_size
+    );
+}
+
+#[derive(Debug, Default)]
+struct RecordingObserver {
+    events: Mutex<Vec<String>>,
+}
+
+impl Observer for RecordingObserver {
+    fn found(&self, crossword: &Crossword) {
+        let (width, height) = crossword.get_width_height();
+        let event = format!("found {}x{}", width, height);
+        self.events.lock().unwrap().push(event);
+    }
+
+    fn improved(&self, _crossword: &Crossword) {
+        self.events.lock().unwrap().push("improved".to_string());
+    }
+
+    fn finished(&self, solution: &Solution) {
+        let event = format!("finished {}", solution.crosswords.len());
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+#[test]
+fn solver_solve_observer() {
+    let words = ["hereby", "exist", "words", "for", "sample", "output"];
+
+    for selection in [Selection::Best, Selection::ParetoFront { crossings: false }] {
+        let observer = Arc::new(RecordingObserver::default());
+        let options = SolverOptions {
+            selection,
+            observer: Some(observer.clone()),
+            ..SolverOptions::default()
+        };
+
+        let solver = Solver::new(&words, options);
+        let solution = solver.solve();
+
+        let events = observer.events.lock().unwrap();
+        assert_eq!(
+            Some(&format!("finished {}", solution.crosswords.len())),
+            events.last()
+        );
+        assert_eq!(
+            1,
+            events
+                .iter()
+                .filter(|event| event.starts_with("finished"))
+                .count()
+        );
+        assert!(events[0].starts_with("found"));
+        assert!(events.iter().any(|event| event == "improved"));
+        for (event, next_event) in events.iter().zip(&events[1..]) {
+            if event == "improved" {
+                assert!(next_event.starts_with("found"));
+            }
+        }
+        for crossword in &solution.crosswords {
+            let (width, height) = crossword.get_width_height();
+            assert!(events.contains(&format!("found {}x{}", width, height)));
+        }
+    }
+}
+
+#[test]
+fn solver_solve_distinct_forms() {
+    let words = ["hereby", "exist", "words", "for", "sample", "output", "for"];
+
+    for threads in [1, 4] {
+        let options = SolverOptions {
+            threads,
+            ..SolverOptions::default()
+        };
+        let solver = Solver::new(&words, options);
+        let solution = solver.solve();
+
+        assert_eq!(1, solution.crosswords.len());
+        assert!(solution.statistics.duplicates > 0);
+    }
+
+    let solver = Solver::new(&["abc", "cab", "bca", "abc"], SolverOptions::default());
+    let crosswords = solver.solve().crosswords;
+    let mut forms: Vec<CanonicalForm> = crosswords
+        .iter()
+        .map(Crossword::get_canonical_form)
+        .collect();
+    forms.sort();
+    forms.dedup();
+    assert_eq!(crosswords.len(), forms.len());
+}
+
+/// Where each word of each crossword found is placed, and how many nodes the search took.
+fn helper_solve_placements(
+    words: &[&str],
+    options: SolverOptions,
+) -> (Vec<Vec<Option<CrossData>>>, u64) {
+    let solver = Solver::new(words, options);
+    let solution = solver.solve();
+    let placements = solution
+        .crosswords
+        .iter()
+        .map(|crossword| {
+            crossword
+                .words
+                .iter()
+                .map(|word| word.cross.clone())
+                .collect()
+        })
+        .collect();
+    (placements, solution.statistics.nodes)
+}
+
+#[test]
+fn solver_solve_transpositions() {
+    let words = ["hereby", "exist", "words", "for", "sample", "output"];
+
+    for selection in [Selection::Best, Selection::ParetoFront { crossings: true }] {
+        for threads in [1, 4] {
+            let solve = |transposition_limit| {
+                let options = SolverOptions {
+                    threads,
+                    selection,
+                    transposition_limit,
+                    ..SolverOptions::default()
+                };
+                helper_solve_placements(&words, options)
+            };
+
+            let (unlimited_crosswords, unlimited_nodes) = solve(DEFAULT_TRANSPOSITION_LIMIT);
+            let (limited_crosswords, limited_nodes) = solve(100);
+            let (crosswords, nodes) = solve(0);
+
+            assert_eq!(crosswords, unlimited_crosswords);
+            assert_eq!(crosswords, limited_crosswords);
+            if threads == 1 {
+                assert!(unlimited_nodes < limited_nodes);
+                assert!(limited_nodes < nodes);
+            }
+        }
+    }
+}
+
+#[test]
+fn solver_solve_heuristics() {
+    let words = ["hereby", "exist", "words", "for", "sample", "output"];
+    let solve = |selection, heuristics| {
+        let options = SolverOptions {
+            selection,
+            heuristics,
+            ..SolverOptions::default()
+        };
+        helper_solve_placements(&words, options)
+    };
+
+    for selection in [Selection::Best, Selection::ParetoFront { crossings: true }] {
+        let (crosswords, _) = solve(selection, vec![]);
+        for heuristics in [
+            vec![Heuristic::Longest],
+            vec![Heuristic::Connectable],
+            vec![Heuristic::Central],
+            vec![Heuristic::Longest, Heuristic::Central],
+        ] {
+            assert_eq!(crosswords, solve(selection, heuristics).0);
+        }
+    }
+
+    let (_, nodes) = solve(Selection::Best, vec![]);
+    let (_, ordered_nodes) = solve(
+        Selection::Best,
+        vec![Heuristic::Longest, Heuristic::Central],
+    );
+    assert!(ordered_nodes < nodes);
+}
@@ -0,0 +1,90 @@
+use crate::crossword::{CrossData, Direction, WordCross};
+
+use super::*;
+
+#[test]
+fn render_blank_grid() {
+    let crossword = helper_crossword();
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="97" height="65" viewBox="0 0 97 65">
+<g font-family="Helvetica, Arial, sans-serif" fill="black">
+<rect x="0.5" y="0.5" width="32" height="32" fill="white" stroke="black" stroke-width="1"/>
+<text x="2.42" y="9.5" font-size="9">1</text>
+<rect x="0.5" y="32.5" width="32" height="32" fill="white" stroke="black" stroke-width="1"/>
+<text x="2.42" y="41.5" font-size="9">2</text>
+<rect x="32.5" y="32.5" width="32" height="32" fill="white" stroke="black" stroke-width="1"/>
+<rect x="64.5" y="32.5" width="32" height="32" fill="white" stroke="black" stroke-width="1"/>
+</g>
+</svg>
+"#;
+    assert_eq!(expected, render(&crossword, &SvgOptions::default()));
+}
+
+#[test]
+fn render_letters() {
+    let crossword = helper_crossword();
+    let options = SvgOptions {
+        cell_size: 10.0,
+        line_width: 2.0,
+        font_family: "\"Times\" & serif".to_string(),
+        letter_font_size: 8.0,
+        show_letters: true,
+        ..SvgOptions::default()
+    };
+
+    let svg = render(&crossword, &options);
+
+    assert!(svg.starts_with(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="32" height="22" viewBox="0 0 32 22">"#
+    ));
+    assert!(svg.contains(r#"<g font-family="&quot;Times&quot; &amp; serif" fill="black">"#));
+    assert!(svg.contains(r#"<rect x="21" y="11" width="10" height="10""#));
+    assert!(svg.contains(
+        r#"<text x="26" y="16.5" font-size="8" text-anchor="middle" dominant-baseline="central">R</text>"#
+    ));
+    assert_eq!(4, svg.matches("text-anchor").count());
+}
+
+#[test]
+fn render_empty_crossword() {
+    let crossword = Crossword { words: vec![] };
+
+    assert_eq!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"1\" height=\"1\" viewBox=\"0 0 1 1\">\n<g font-family=\"Helvetica, Arial, sans-serif\" fill=\"black\">\n</g>\n</svg>\n",
+        render(&crossword, &SvgOptions::default())
+    );
+}
+
+#[test]
+fn test_escape() {
+    assert_eq!("a&lt;b&gt;&amp;&quot;c", escape("a<b>&\"c"));
+}
+
+fn helper_crossword() -> Crossword<'static> {
+    Crossword {
+        words: vec![
+            WordCross {
+                word: "for",
+                clue: None,
+                display: None,
+                cross: Some(CrossData {
+                    row: 0,
+                    start_point: 0,
+                    direction: Direction::Across,
+                    order: 0,
+                }),
+            },
+            WordCross {
+                word: "of",
+                clue: None,
+                display: None,
+                cross: Some(CrossData {
+                    row: 0,
+                    start_point: -1,
+                    direction: Direction::Down,
+                    order: 1,
+                }),
+            },
+        ],
+    }
+}
@@ -0,0 +1,26 @@
+use super::*;
+
+#[test]
+fn transpositions_visit() {
+    let mut transpositions = Transpositions::new(2);
+    assert!(transpositions.visited.is_empty());
+
+    assert!(!transpositions.visit(1));
+    assert!(transpositions.visit(1));
+    assert!(!transpositions.visit(2));
+    assert_eq!(2, transpositions.visited.len());
+
+    assert!(!transpositions.visit(3));
+    assert!(!transpositions.visit(3));
+    assert_eq!(2, transpositions.visited.len());
+    assert!(transpositions.visit(2));
+}
+
+#[test]
+fn transpositions_disabled() {
+    let mut transpositions = Transpositions::new(0);
+
+    assert!(!transpositions.visit(1));
+    assert!(!transpositions.visit(1));
+    assert!(transpositions.visited.is_empty());
+}
@@ -0,0 +1,38 @@
+use std::collections::HashSet;
+
+/// Partial crosswords already searched, by [`Grid::key`], so that a layout reached again by
+/// placing the same words in another order is not searched twice.
+///
+/// [`Grid::key`]: crate::grid::Grid::key
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Transpositions {
+    visited: HashSet<u128>,
+    limit: usize,
+}
+
+impl Transpositions {
+    /// Remembers at most `limit` layouts, after which new layouts are searched without being
+    /// remembered.
+    pub fn new(limit: usize) -> Transpositions {
+        Transpositions {
+            visited: HashSet::new(),
+            limit,
+        }
+    }
+
+    /// Marks the layout as searched, and returns whether it already was.
+    pub fn visit(&mut self, key: u128) -> bool {
+        if self.visited.contains(&key) {
+            true
+        } else {
+            if self.visited.len() < self.limit {
+                self.visited.insert(key);
+            }
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "./tests_transpositions.rs"]
+mod tests_transpositions;